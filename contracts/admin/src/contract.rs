@@ -1,55 +1,527 @@
 use crate::error::ContractError;
-use crate::msg::{AdminsListResp, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{ADMINS, DONATION_DENOM, VERIFIER, VERSION};
+use crate::migrations::{migrate_state, migration_path, LATEST_VERSION};
+use crate::msg::{
+    AdminCountResp, AdminInfoResp, AdminProfile, AdminsListResp, BondResp,
+    ConfigResp, ExecuteMsg, HookPolicy, InstantiateMsg, IsAdminResp, LastMigrationResp,
+    MigrateMsg, MigrationAttempt, MigrationHistoryResp, MigrationOutcome, MigrationPolicy,
+    PendingExit, PendingExitResp, PendingExitsResp, ProposalQueueResp, QueryMsg, QueuedProposal, QuotaResp,
+    SimulateMigrationResp, VersionHistoryResp, VersionRecord,
+};
+use crate::state::{
+    Ballot, Exit, MigrationApproval, Proposal, Votes, ADDITIONS, ADMINS, ADMIN_COUNT, BALLOTS, BONDS, BOND_AMOUNT,
+    DONATION_DENOM, EXITS, HOOKS, HOOK_POLICY, INVITATIONS, INVITE_QUOTA, LEAVE_COOLDOWN,
+    MAX_ADMINS, MAX_VOTING_PERIOD, MEMBERS, MIN_ADMINS, OWNER, PENDING_OWNER, PROPOSALS,
+    PROPOSAL_COUNT, QUEUED,
+    MIGRATION_DEADLINES, MIGRATION_HISTORY, MIGRATION_PROPOSALS, SLASH_VOTES, THRESHOLD, TIMELOCK, TOTAL, VERIFIER, VERSION, VERSION_HISTORY, VETOER,
+};
 use cosmwasm_std::{
-    coins, to_binary, Addr, BankMsg, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, Reply, Response, StdResult, SubMsg, WasmMsg
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsg, SubMsgResult, Timestamp, Uint128, WasmMsg
 };
+use cw3::{ProposalListResponse, ProposalResponse, Status, VoteInfo, VoteListResponse, VoteResponse};
+use cw4::{
+    Member, MemberChangedHookMsg, MemberDiff, MemberListResponse, MemberResponse,
+    TotalWeightResponse,
+};
+use cw_storage_plus::Bound;
+use cw_utils::{Duration, Expiration, ThresholdResponse};
+use semver::Version;
+use std::cmp::Ordering;
 use query::get_version;
 
+const CONTRACT_NAME: &str = "crates.io:admin";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub const OLD_VERSION: u32 = 1;
-pub const NEW_VERSION: u32 = 2;
+pub const NEW_VERSION: u32 = LATEST_VERSION;
 
 const VALIDATE_MIGRATION_REPLY_ID: u64 = 1;
+const MEMBER_HOOK_REPLY_ID: u64 = 2;
+
+pub(crate) const DEFAULT_MIN_ADMINS: u32 = 1;
+pub(crate) const DEFAULT_LEAVE_COOLDOWN: u64 = 0;
+pub(crate) const DEFAULT_MAX_VOTING_PERIOD: Duration = Duration::Time(7 * 24 * 60 * 60);
+
+const MAX_DISPLAY_NAME_LEN: usize = 64;
+const MAX_AVATAR_URL_LEN: usize = 256;
+const MAX_CONTACT_LEN: usize = 128;
+const MAX_BIO_LEN: usize = 512;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    OWNER.save(deps.storage, &Some(owner))?;
+    PENDING_OWNER.save(deps.storage, &None)?;
+
+    ADMIN_COUNT.save(deps.storage, &0)?;
+    TOTAL.save(deps.storage, &0)?;
     for addr in msg.admins {
         let admin = deps.api.addr_validate(&addr)?;
-        ADMINS.save(deps.storage, &admin, &Empty {})?;
+        if !ADMINS.has(deps.storage, &admin) {
+            seat_admin(deps.storage, env.block.height, &admin)?;
+        }
     }
 
+    if let Some(max) = msg.max_admins {
+        if ADMIN_COUNT.load(deps.storage)? > max {
+            return Err(ContractError::MaxAdmins { max });
+        }
+    }
+
+    MAX_ADMINS.save(deps.storage, &msg.max_admins)?;
+    INVITE_QUOTA.save(deps.storage, &msg.invite_quota)?;
+    HOOK_POLICY.save(deps.storage, &msg.hook_policy.unwrap_or(HookPolicy::Revert))?;
+
+    let threshold = msg.threshold.unwrap_or(cw_utils::Threshold::AbsolutePercentage {
+        percentage: Decimal::percent(51),
+    });
+    threshold.validate(TOTAL.load(deps.storage)?)?;
+    THRESHOLD.save(deps.storage, &threshold)?;
+    MAX_VOTING_PERIOD.save(
+        deps.storage,
+        &msg.max_voting_period.unwrap_or(DEFAULT_MAX_VOTING_PERIOD),
+    )?;
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    TIMELOCK.save(deps.storage, &msg.timelock.unwrap_or_default())?;
+    let vetoer = msg
+        .vetoer
+        .map(|vetoer| deps.api.addr_validate(&vetoer))
+        .transpose()?;
+    VETOER.save(deps.storage, &vetoer)?;
+    // Donations are split between the admins, so at least one has to stay
+    let admins = ADMIN_COUNT.load(deps.storage)?;
+    if let Some(min) = msg.min_admins {
+        if min == 0 || min > admins {
+            return Err(ContractError::InvalidMinAdmins { min, admins });
+        }
+    }
+    MIN_ADMINS.save(
+        deps.storage,
+        &msg.min_admins.unwrap_or(DEFAULT_MIN_ADMINS),
+    )?;
+    let leave_cooldown = msg.leave_cooldown.unwrap_or(DEFAULT_LEAVE_COOLDOWN);
+    let bond = msg.bond.unwrap_or_default();
+    // Without a cooldown a bonded admin could leave before a slash vote passes
+    if !bond.is_zero() && leave_cooldown == 0 {
+        return Err(ContractError::BondWithoutCooldown {});
+    }
+    LEAVE_COOLDOWN.save(deps.storage, &leave_cooldown)?;
+    // Initial admins are seated without posting a bond
+    BOND_AMOUNT.save(deps.storage, &bond)?;
+
     DONATION_DENOM.save(deps.storage, &msg.donation_denom)?;
-    VERSION.save(deps.storage, &OLD_VERSION)?;
+    // Fresh state is already in the latest layout, so no steps are left to run
+    VERSION.save(deps.storage, &LATEST_VERSION)?;
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    record_version(deps.storage, &env, LATEST_VERSION)?;
     VERIFIER.save(deps.storage, &deps.api.addr_validate(&msg.verifier)?)?;
 
     Ok(Response::new())
 }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     use QueryMsg::*;
 
     match msg {
-        AdminsList {} => to_binary(&query::admins_list(deps)?),
-        GetVersion {} => to_binary(&query::get_version(deps)?)
+        AdminsList { start_after, limit } => {
+            to_json_binary(&query::admins_list(deps, start_after, limit)?)
+        }
+        AdminInfo { addr } => to_json_binary(&query::admin_info(deps, addr)?),
+        AdminCount {} => to_json_binary(&query::admin_count(deps)?),
+        Member { addr, at_height } => to_json_binary(&query::member(deps, addr, at_height)?),
+        ListMembers { start_after, limit } => {
+            to_json_binary(&query::list_members(deps, start_after, limit)?)
+        }
+        TotalWeight {} => to_json_binary(&query::total_weight(deps)?),
+        Hooks {} => to_json_binary(&HOOKS.query_hooks(deps)?),
+        IsAdmin { addr } => to_json_binary(&query::is_admin(deps, addr)?),
+        PendingExit { addr } => to_json_binary(&query::pending_exit(deps, addr)?),
+        PendingExits { start_after, limit } => {
+            to_json_binary(&query::pending_exits(deps, start_after, limit)?)
+        }
+        Bond { addr } => to_json_binary(&query::bond(deps, addr)?),
+        Quota { addr } => to_json_binary(&query::quota(deps, env, addr)?),
+        Threshold {} => to_json_binary(&query::threshold(deps)?),
+        Proposal { proposal_id } => to_json_binary(&query::proposal(deps, env, proposal_id)?),
+        ListProposals { start_after, limit } => {
+            to_json_binary(&query::list_proposals(deps, env, start_after, limit)?)
+        }
+        Vote { proposal_id, voter } => to_json_binary(&query::vote(deps, proposal_id, voter)?),
+        ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::list_votes(deps, proposal_id, start_after, limit)?),
+        ProposalQueue { start_after, limit } => {
+            to_json_binary(&query::proposal_queue(deps, start_after, limit)?)
+        }
+        Config {} => to_json_binary(&query::config(deps)?),
+        ContractVersion {} => to_json_binary(&cw2::get_contract_version(deps.storage)?),
+        VersionHistory { start_after, limit } => {
+            to_json_binary(&query::version_history(deps, start_after, limit)?)
+        }
+        MigrationHistory { start_after, limit } => {
+            to_json_binary(&query::migration_history(deps, start_after, limit)?)
+        }
+        LastMigration {} => to_json_binary(&query::last_migration(deps)?),
+        SimulateMigration {
+            reason,
+            target_version,
+            attestation,
+        } => to_json_binary(&query::simulate_migration(
+            deps,
+            env,
+            reason,
+            target_version,
+            attestation,
+        )?),
+        GetVersion {} => to_json_binary(&query::get_version(deps)?)
     }
 }
 
 mod query {
     use super::*;
 
-    pub fn admins_list(deps: Deps) -> StdResult<AdminsListResp> {
+    pub fn admins_list(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AdminsListResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
         let admins: Result<Vec<_>, _> = ADMINS
-            .keys(deps.storage, None, None, Order::Ascending)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
             .collect();
-        let admins = admins?;
-        let resp = AdminsListResp { admins };
+        let (admins, profiles) = admins?.into_iter().unzip();
+        let resp = AdminsListResp { admins, profiles };
         Ok(resp)
     }
 
+    pub fn admin_info(deps: Deps, addr: String) -> StdResult<AdminInfoResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let profile = ADMINS.load(deps.storage, &addr)?;
+        Ok(AdminInfoResp { addr, profile })
+    }
+
+    pub fn admin_count(deps: Deps) -> StdResult<AdminCountResp> {
+        let count = ADMIN_COUNT.load(deps.storage)?;
+        Ok(AdminCountResp { count })
+    }
+
+    pub fn member(deps: Deps, addr: String, at_height: Option<u64>) -> StdResult<MemberResponse> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let weight = match at_height {
+            Some(height) => MEMBERS.may_load_at_height(deps.storage, &addr, height)?,
+            None => MEMBERS.may_load(deps.storage, &addr)?,
+        };
+        Ok(MemberResponse { weight })
+    }
+
+    pub fn list_members(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<MemberListResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let members: Result<Vec<_>, _> = MEMBERS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(addr, weight)| Member {
+                    addr: addr.into(),
+                    weight,
+                })
+            })
+            .collect();
+        Ok(MemberListResponse { members: members? })
+    }
+
+    pub fn total_weight(deps: Deps) -> StdResult<TotalWeightResponse> {
+        let weight = TOTAL.load(deps.storage)?;
+        Ok(TotalWeightResponse { weight })
+    }
+
+    pub fn is_admin(deps: Deps, addr: String) -> StdResult<IsAdminResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let is_admin = ADMINS.has(deps.storage, &addr);
+        Ok(IsAdminResp { is_admin })
+    }
+
+    pub fn pending_exit(deps: Deps, addr: String) -> StdResult<PendingExitResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let exit = EXITS
+            .may_load(deps.storage, &addr)?
+            .map(|exit| PendingExit {
+                addr,
+                ready_at: exit.ready_at,
+                accrued: exit.accrued,
+            });
+        Ok(PendingExitResp { exit })
+    }
+
+    pub fn pending_exits(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<PendingExitsResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let exits: Result<Vec<_>, _> = EXITS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(addr, exit)| PendingExit {
+                    addr,
+                    ready_at: exit.ready_at,
+                    accrued: exit.accrued,
+                })
+            })
+            .collect();
+        Ok(PendingExitsResp { exits: exits? })
+    }
+
+    pub fn bond(deps: Deps, addr: String) -> StdResult<BondResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let amount = BONDS.may_load(deps.storage, &addr)?.unwrap_or_default();
+        let invited = INVITATIONS.has(deps.storage, &addr);
+        Ok(BondResp { amount, invited })
+    }
+
+    pub fn quota(deps: Deps, env: Env, addr: String) -> StdResult<QuotaResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let quota = INVITE_QUOTA.load(deps.storage)?;
+        let used = recent_additions(deps.storage, &env, &addr)?.len() as u32;
+        let remaining = quota.map(|quota| quota.max_additions.saturating_sub(used));
+        Ok(QuotaResp { used, remaining })
+    }
+
+    pub fn threshold(deps: Deps) -> StdResult<ThresholdResponse> {
+        let total_weight = TOTAL.load(deps.storage)?;
+        let threshold = THRESHOLD.load(deps.storage)?;
+        Ok(threshold.to_response(total_weight))
+    }
+
+    pub fn proposal(deps: Deps, env: Env, proposal_id: u64) -> StdResult<ProposalResponse> {
+        let prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        Ok(proposal_to_response(&env.block, proposal_id, prop))
+    }
+
+    pub fn list_proposals(
+        deps: Deps,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ProposalListResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let proposals: Result<Vec<_>, _> = PROPOSALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(id, prop)| proposal_to_response(&env.block, id, prop)))
+            .collect();
+        Ok(ProposalListResponse {
+            proposals: proposals?,
+        })
+    }
+
+    fn proposal_to_response(block: &BlockInfo, id: u64, prop: Proposal) -> ProposalResponse {
+        ProposalResponse {
+            id,
+            status: prop.current_status(block),
+            title: prop.title,
+            description: prop.description,
+            msgs: prop.msgs,
+            expires: prop.expires,
+            threshold: prop.threshold.to_response(prop.total_weight),
+        }
+    }
+
+    pub fn vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+        let vote = BALLOTS
+            .may_load(deps.storage, (proposal_id, &voter))?
+            .map(|ballot| VoteInfo {
+                proposal_id,
+                voter: voter.into(),
+                vote: ballot.vote,
+                weight: ballot.weight,
+            });
+        Ok(VoteResponse { vote })
+    }
+
+    pub fn list_votes(
+        deps: Deps,
+        proposal_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VoteListResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let votes: Result<Vec<_>, _> = BALLOTS
+            .prefix(proposal_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(voter, ballot)| VoteInfo {
+                    proposal_id,
+                    voter: voter.into(),
+                    vote: ballot.vote,
+                    weight: ballot.weight,
+                })
+            })
+            .collect();
+        Ok(VoteListResponse { votes: votes? })
+    }
+
+    pub fn proposal_queue(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ProposalQueueResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let proposals: Result<Vec<_>, _> = QUEUED
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(proposal_id, executable_at)| QueuedProposal {
+                    proposal_id,
+                    executable_at,
+                })
+            })
+            .collect();
+        Ok(ProposalQueueResp {
+            proposals: proposals?,
+        })
+    }
+
+    pub fn config(deps: Deps) -> StdResult<ConfigResp> {
+        Ok(ConfigResp {
+            owner: OWNER.load(deps.storage)?,
+            pending_owner: PENDING_OWNER.load(deps.storage)?,
+            donation_denom: DONATION_DENOM.load(deps.storage)?,
+            verifier: VERIFIER.load(deps.storage)?,
+            min_admins: MIN_ADMINS.load(deps.storage)?,
+            leave_cooldown: LEAVE_COOLDOWN.load(deps.storage)?,
+            bond: BOND_AMOUNT.load(deps.storage)?,
+            max_admins: MAX_ADMINS.load(deps.storage)?,
+            invite_quota: INVITE_QUOTA.load(deps.storage)?,
+            hook_policy: HOOK_POLICY.load(deps.storage)?,
+            threshold: THRESHOLD.load(deps.storage)?,
+            max_voting_period: MAX_VOTING_PERIOD.load(deps.storage)?,
+            timelock: TIMELOCK.load(deps.storage)?,
+            vetoer: VETOER.load(deps.storage)?,
+        })
+    }
+
+    pub fn version_history(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<VersionHistoryResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let history: Result<Vec<_>, _> = VERSION_HISTORY
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, record)| record))
+            .collect();
+        Ok(VersionHistoryResp { history: history? })
+    }
+
+    pub fn migration_history(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<MigrationHistoryResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let attempts: Result<Vec<_>, _> = MIGRATION_HISTORY
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, attempt)| attempt))
+            .collect();
+        Ok(MigrationHistoryResp {
+            attempts: attempts?,
+        })
+    }
+
+    pub fn last_migration(deps: Deps) -> StdResult<LastMigrationResp> {
+        let attempt = MIGRATION_HISTORY
+            .range(deps.storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, attempt)| attempt);
+        Ok(LastMigrationResp { attempt })
+    }
+
+    pub fn simulate_migration(
+        deps: Deps,
+        env: Env,
+        reason: String,
+        target_version: Option<u32>,
+        attestation: Option<verifier::msg::Attestation>,
+    ) -> StdResult<SimulateMigrationResp> {
+        let current_version = get_version(deps)?;
+        let target_version = target_version.unwrap_or(NEW_VERSION);
+        let verifier = VERIFIER.load(deps.storage)?;
+
+        let verdict: verifier::msg::MigrationVerdict = deps.querier.query_wasm_smart(
+            &verifier,
+            &verifier::msg::QueryMsg::ValidateMigration {
+                current_version,
+                new_version: target_version,
+                reason,
+                client: Some(env.contract.address.to_string()),
+                attestation,
+            },
+        )?;
+
+        let mut reasons = verdict.reasons;
+        if target_version > LATEST_VERSION {
+            reasons.push(format!("No migration steps to version {}", target_version));
+        }
+
+        Ok(SimulateMigrationResp {
+            current_version,
+            target_version,
+            verifier,
+            accepted: reasons.is_empty(),
+            reasons,
+        })
+    }
+
     pub fn get_version(deps: Deps) -> StdResult<u32> {
         let resp = VERSION.load(deps.storage)?;
         Ok(resp)
@@ -58,16 +530,45 @@ mod query {
 
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     use ExecuteMsg::*;
 
     match msg {
-        AddMembers { admins } => exec::add_members(deps, info, admins),
-        Leave {} => exec::leave(deps, info).map_err(Into::into),
+        AddMembers { admins } => exec::add_members(deps, env, info, admins),
+        UpdateMembers { remove, add } => exec::update_members(deps, env, info, remove, add),
+        AddHook { addr } => exec::add_hook(deps, info, addr),
+        RemoveHook { addr } => exec::remove_hook(deps, info, addr),
+        Leave { successor } => exec::leave(deps, env, info, successor),
+        RequestLeave {} => exec::request_leave(deps, env, info),
+        CompleteLeave {} => exec::complete_leave(deps, env, info),
+        PostBond {} => exec::post_bond(deps, env, info),
+        Slash { addr, percent } => exec::slash(deps, info, addr, percent),
+        UpdateProfile { profile } => exec::update_profile(deps, info, profile),
+        Propose {
+            title,
+            description,
+            msgs,
+            latest,
+        } => exec::propose(deps, env, info, title, description, msgs, latest),
+        ProposeMigration {
+            new_code_id,
+            reason,
+        } => exec::propose_migration(deps, env, info, new_code_id, reason),
+        Vote { proposal_id, vote } => exec::vote(deps, env, info, proposal_id, vote),
+        Execute { proposal_id } => exec::execute_proposal(deps, env, info, proposal_id),
+        Close { proposal_id } => exec::close(deps, env, info, proposal_id),
+        Veto { proposal_id } => exec::veto(deps, info, proposal_id),
         Donate {} => exec::donate(deps, info),
+        UpdateConfig {
+            donation_denom,
+            verifier,
+        } => exec::update_config(deps, env, info, donation_denom, verifier),
+        TransferOwnership { new_owner } => exec::transfer_ownership(deps, info, new_owner),
+        AcceptOwnership {} => exec::accept_ownership(deps, info),
+        RenounceOwnership {} => exec::renounce_ownership(deps, info),
     }
 }
 
@@ -76,28 +577,70 @@ mod exec {
 
     pub fn add_members(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         admins: Vec<String>,
     ) -> Result<Response, ContractError> {
-        if !ADMINS.has(deps.storage, &info.sender) {
+        // Passed proposals act through the contract itself
+        let by_proposal = info.sender == env.contract.address;
+        if !by_proposal && !ADMINS.has(deps.storage, &info.sender) {
             return Err(ContractError::Unauthorized {
                 sender: info.sender,
             });
         }
 
+        if let Some(max) = MAX_ADMINS.load(deps.storage)? {
+            if ADMIN_COUNT.load(deps.storage)? + admins.len() as u32 > max {
+                return Err(ContractError::MaxAdmins { max });
+            }
+        }
+
+        // Limits how fast a single admin can dilute the others' share
+        if let Some(quota) = INVITE_QUOTA.load(deps.storage)?.filter(|_| !by_proposal) {
+            let mut additions = recent_additions(deps.storage, &env, &info.sender)?;
+            if additions.len() + admins.len() > quota.max_additions as usize {
+                return Err(ContractError::QuotaExceeded {
+                    max_additions: quota.max_additions,
+                    window: quota.window,
+                });
+            }
+            additions.extend(admins.iter().map(|_| env.block.time));
+            ADDITIONS.save(deps.storage, &info.sender, &additions)?;
+        }
+
+        // When a bond is configured new admins are only invited, and take their
+        // seat once they post it with `PostBond`
+        let bonded = !BOND_AMOUNT.load(deps.storage)?.is_zero();
+
+        let admins = admins
+            .iter()
+            .map(|admin| deps.api.addr_validate(admin))
+            .collect::<StdResult<Vec<_>>>()?;
+
         // Prevents admins front-running donations and stealing a bigger % of the
         // donations by duplicating their address.
+        let mut diffs = vec![];
         for new_admin in &admins {
-            if ADMINS.has(deps.storage, &Addr::unchecked(new_admin)) {
-                return Err(ContractError::AdminExists { admin: Addr::unchecked(new_admin) });
+            if ADMINS.has(deps.storage, new_admin) {
+                return Err(ContractError::AdminExists { admin: new_admin.clone() });
+            }
+            if INVITATIONS.has(deps.storage, new_admin) {
+                return Err(ContractError::AlreadyInvited { addr: new_admin.clone() });
+            }
+
+            if bonded {
+                INVITATIONS.save(deps.storage, new_admin, &Empty {})?;
+            } else {
+                diffs.push(seat_admin(deps.storage, env.block.height, new_admin)?);
             }
-            ADMINS.save(deps.storage, &Addr::unchecked(new_admin), &Empty {})?;
         }
 
+        let event_type = if bonded { "admin_invited" } else { "admin_added" };
         let events = admins
             .iter()
-            .map(|admin| Event::new("admin_added").add_attribute("addr", admin));
+            .map(|admin| Event::new(event_type).add_attribute("addr", admin));
         let resp = Response::new()
+            .add_submessages(membership_hooks(deps.storage, diffs)?)
             .add_events(events)
             .add_attribute("action", "add_members")
             .add_attribute("added_count", admins.len().to_string());
@@ -105,13 +648,673 @@ mod exec {
         Ok(resp)
     }
 
-    pub fn leave(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
-        ADMINS.remove(deps.storage, &info.sender);
+    pub fn update_members(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        remove: Vec<String>,
+        add: Vec<Member>,
+    ) -> Result<Response, ContractError> {
+        // Removing other admins needs the approval of the group
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        // Every admin seat carries the same weight
+        if let Some(member) = add.iter().find(|member| member.weight != 1) {
+            return Err(ContractError::InvalidWeight {
+                weight: member.weight,
+            });
+        }
 
+        let min = MIN_ADMINS.load(deps.storage)?;
+        let cooldown = LEAVE_COOLDOWN.load(deps.storage)?;
+        let mut diffs = vec![];
+        for addr in remove {
+            let addr = deps.api.addr_validate(&addr)?;
+            if !ADMINS.has(deps.storage, &addr) {
+                continue;
+            }
+            if ADMIN_COUNT.load(deps.storage)? <= min {
+                return Err(ContractError::MinAdmins { min });
+            }
+
+            diffs.push(vacate_seat(deps.storage, env.block.height, &addr)?);
+
+            // The bond and held donations are only paid out through `CompleteLeave`,
+            // so the bond stays slashable for the cooldown
+            if BONDS.has(deps.storage, &addr) && !EXITS.has(deps.storage, &addr) {
+                let exit = Exit {
+                    ready_at: env.block.time.plus_seconds(cooldown),
+                    accrued: Uint128::zero(),
+                };
+                EXITS.save(deps.storage, &addr, &exit)?;
+            }
+        }
+
+        let events = diffs
+            .iter()
+            .map(|diff| Event::new("admin_removed").add_attribute("addr", &diff.key));
         let resp = Response::new()
+            .add_submessages(membership_hooks(deps.storage, diffs.clone())?)
+            .add_events(events)
+            .add_attribute("action", "update_members")
+            .add_attribute("removed_count", diffs.len().to_string());
+
+        if add.is_empty() {
+            return Ok(resp);
+        }
+
+        let admins = add.into_iter().map(|member| member.addr).collect();
+        let added = add_members(deps.branch(), env, info, admins)?;
+        Ok(resp
+            .add_submessages(added.messages)
+            .add_events(added.events)
+            .add_attributes(added.attributes.into_iter().filter(|attr| attr.key != "action")))
+    }
+
+    pub fn add_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        if !ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        let hook = deps.api.addr_validate(&addr)?;
+        HOOKS.add_hook(deps.storage, hook)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("hook", addr);
+
+        Ok(resp)
+    }
+
+    pub fn remove_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        if !ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        let hook = deps.api.addr_validate(&addr)?;
+        HOOKS.remove_hook(deps.storage, hook)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("hook", addr);
+
+        Ok(resp)
+    }
+
+    pub fn leave(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        successor: Option<String>,
+    ) -> Result<Response, ContractError> {
+        if !ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        if EXITS.has(deps.storage, &info.sender) {
+            return Err(ContractError::ExitPending { addr: info.sender });
+        }
+
+        let mut resp = Response::new()
             .add_attribute("action", "leave")
             .add_attribute("sender", info.sender.as_str());
 
+        match successor {
+            // The seat is handed over, so the admin count does not change
+            Some(successor) => {
+                // Successors of bonded seats have to be invited and post their own bond
+                if !BOND_AMOUNT.load(deps.storage)?.is_zero() {
+                    return Err(ContractError::BondedSuccession {});
+                }
+
+                let successor = deps.api.addr_validate(&successor)?;
+                if ADMINS.has(deps.storage, &successor) {
+                    return Err(ContractError::AdminExists { admin: successor });
+                }
+
+                if INVITATIONS.has(deps.storage, &successor) {
+                    return Err(ContractError::AlreadyInvited { addr: successor });
+                }
+
+                let (left, _) = unseat_admin(deps.storage, env.block.height, &info.sender)?;
+                let joined = seat_admin(deps.storage, env.block.height, &successor)?;
+
+                resp = resp
+                    .add_submessages(membership_hooks(deps.storage, vec![left, joined])?)
+                    .add_event(Event::new("admin_added").add_attribute("addr", successor.as_str()))
+                    .add_attribute("successor", successor.as_str());
+            }
+            None => {
+                let cooldown = LEAVE_COOLDOWN.load(deps.storage)?;
+                if cooldown > 0 {
+                    return Err(ContractError::LeaveCooldown { cooldown });
+                }
+
+                let min = MIN_ADMINS.load(deps.storage)?;
+                let count = ADMIN_COUNT.load(deps.storage)?;
+                if count <= min {
+                    return Err(ContractError::MinAdmins { min });
+                }
+
+                let (left, bond) = unseat_admin(deps.storage, env.block.height, &info.sender)?;
+                resp = resp.add_submessages(membership_hooks(deps.storage, vec![left])?);
+                if !bond.is_zero() {
+                    let denom = DONATION_DENOM.load(deps.storage)?;
+                    resp = resp
+                        .add_message(BankMsg::Send {
+                            to_address: info.sender.to_string(),
+                            amount: coins(bond.u128(), denom),
+                        })
+                        .add_attribute("bond_returned", bond.to_string());
+                }
+            }
+        }
+
+        Ok(resp)
+    }
+
+    pub fn request_leave(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        if !ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        if EXITS.has(deps.storage, &info.sender) {
+            return Err(ContractError::ExitPending { addr: info.sender });
+        }
+
+        let min = MIN_ADMINS.load(deps.storage)?;
+        if ADMIN_COUNT.load(deps.storage)? <= min {
+            return Err(ContractError::MinAdmins { min });
+        }
+
+        let ready_at = env.block.time.plus_seconds(LEAVE_COOLDOWN.load(deps.storage)?);
+        let exit = Exit {
+            ready_at,
+            accrued: Uint128::zero(),
+        };
+        EXITS.save(deps.storage, &info.sender, &exit)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "request_leave")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("ready_at", ready_at.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn complete_leave(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let exit = EXITS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or_else(|| ContractError::NoPendingExit {
+                addr: info.sender.clone(),
+            })?;
+        if env.block.time < exit.ready_at {
+            return Err(ContractError::CooldownNotFinished {
+                ready_at: exit.ready_at,
+            });
+        }
+
+        // Admins removed with `UpdateMembers` have already lost their seat and only
+        // settle what they are owed
+        let mut diffs = vec![];
+        if ADMINS.has(deps.storage, &info.sender) {
+            // Other admins may have left during the cooldown
+            let min = MIN_ADMINS.load(deps.storage)?;
+            let count = ADMIN_COUNT.load(deps.storage)?;
+            if count <= min {
+                return Err(ContractError::MinAdmins { min });
+            }
+            diffs.push(vacate_seat(deps.storage, env.block.height, &info.sender)?);
+        }
+
+        let payout = settle_exit(deps.storage, &info.sender)?;
+        let bond = payout - exit.accrued;
+
+        let mut resp = Response::new()
+            .add_submessages(membership_hooks(deps.storage, diffs)?)
+            .add_attribute("action", "complete_leave")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("settled", exit.accrued.to_string())
+            .add_attribute("bond_returned", bond.to_string());
+
+        if !payout.is_zero() {
+            let denom = DONATION_DENOM.load(deps.storage)?;
+            resp = resp.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(payout.u128(), denom),
+            });
+        }
+
+        Ok(resp)
+    }
+
+    pub fn post_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        if !INVITATIONS.has(deps.storage, &info.sender) {
+            return Err(ContractError::NotInvited { addr: info.sender });
+        }
+
+        let denom = DONATION_DENOM.load(deps.storage)?;
+        let expected = BOND_AMOUNT.load(deps.storage)?;
+        let bond = cw_utils::must_pay(&info, &denom)?;
+        if bond != expected {
+            return Err(ContractError::InvalidBond { expected });
+        }
+
+        if let Some(max) = MAX_ADMINS.load(deps.storage)? {
+            if ADMIN_COUNT.load(deps.storage)? >= max {
+                return Err(ContractError::MaxAdmins { max });
+            }
+        }
+
+        INVITATIONS.remove(deps.storage, &info.sender);
+        let joined = seat_admin(deps.storage, env.block.height, &info.sender)?;
+        BONDS.save(deps.storage, &info.sender, &bond)?;
+
+        let resp = Response::new()
+            .add_submessages(membership_hooks(deps.storage, vec![joined])?)
+            .add_event(Event::new("admin_added").add_attribute("addr", info.sender.as_str()))
+            .add_attribute("action", "post_bond")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("bond", bond.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn slash(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        percent: u64,
+    ) -> Result<Response, ContractError> {
+        if !ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        if percent == 0 || percent > 100 {
+            return Err(ContractError::InvalidPercent { percent });
+        }
+
+        let target = deps.api.addr_validate(&addr)?;
+        // Admins cannot vote on their own slashing
+        if target == info.sender {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        let bond = BONDS.may_load(deps.storage, &target)?.unwrap_or_default();
+        if bond.is_zero() {
+            return Err(ContractError::NoBond { addr: target });
+        }
+
+        let mut votes = SLASH_VOTES
+            .may_load(deps.storage, (&target, percent))?
+            .unwrap_or_default();
+        if votes.contains(&info.sender) {
+            return Err(ContractError::AlreadyVoted { voter: info.sender });
+        }
+        // Only the votes of current admins count
+        votes.retain(|voter| ADMINS.has(deps.storage, voter));
+        votes.push(info.sender.clone());
+
+        let resp = Response::new()
+            .add_attribute("action", "slash")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("addr", target.as_str())
+            .add_attribute("percent", percent.to_string())
+            .add_attribute("votes", votes.len().to_string());
+
+        // A strict majority of the other admins has to agree on the same percent
+        let mut voters = ADMIN_COUNT.load(deps.storage)?;
+        if ADMINS.has(deps.storage, &target) {
+            voters -= 1;
+        }
+        if (votes.len() as u32) * 2 <= voters {
+            SLASH_VOTES.save(deps.storage, (&target, percent), &votes)?;
+            return Ok(resp);
+        }
+        SLASH_VOTES.remove(deps.storage, (&target, percent));
+
+        let slashed = bond.multiply_ratio(percent, 100u64);
+        BONDS.save(deps.storage, &target, &(bond - slashed))?;
+
+        let denom = DONATION_DENOM.load(deps.storage)?;
+        let recipients: Result<Vec<_>, _> = ADMINS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .filter(|admin| admin.as_ref() != Ok(&target))
+            .collect();
+        let recipients = recipients?;
+        let slashed_per_admin = slashed.u128() / (recipients.len() as u128);
+        let messages = distribute(deps.storage, recipients, slashed_per_admin, &denom)?;
+
+        let resp = resp
+            .add_messages(messages)
+            .add_event(
+                Event::new("admin_slashed")
+                    .add_attribute("addr", target.as_str())
+                    .add_attribute("amount", slashed.to_string()),
+            );
+
+        Ok(resp)
+    }
+
+    pub fn update_profile(
+        deps: DepsMut,
+        info: MessageInfo,
+        profile: AdminProfile,
+    ) -> Result<Response, ContractError> {
+        if !ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        let fields = [
+            ("display_name", &profile.display_name, MAX_DISPLAY_NAME_LEN),
+            ("avatar_url", &profile.avatar_url, MAX_AVATAR_URL_LEN),
+            ("contact", &profile.contact, MAX_CONTACT_LEN),
+            ("bio", &profile.bio, MAX_BIO_LEN),
+        ];
+        for (field, value, max) in fields {
+            if value.as_ref().map_or(0, |value| value.chars().count()) > max {
+                return Err(ContractError::ProfileFieldTooLong {
+                    field: field.to_owned(),
+                    max,
+                });
+            }
+        }
+
+        ADMINS.save(deps.storage, &info.sender, &profile)?;
+
+        let resp = Response::new()
+            .add_event(Event::new("profile_updated").add_attribute("addr", info.sender.as_str()))
+            .add_attribute("action", "update_profile")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn propose(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg>,
+        latest: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let (id, prop) = open_proposal(deps, &env, &info.sender, title, description, msgs, latest)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "propose")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("proposal_id", id.to_string())
+            .add_attribute("status", format!("{:?}", prop.status));
+
+        Ok(resp)
+    }
+
+    pub fn propose_migration(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_code_id: u64,
+        reason: String,
+    ) -> Result<Response, ContractError> {
+        let title = format!("Migrate to code {}", new_code_id);
+        let (id, prop) = open_proposal(
+            deps.branch(),
+            &env,
+            &info.sender,
+            title,
+            reason.clone(),
+            vec![],
+            None,
+        )?;
+        let approval = MigrationApproval {
+            new_code_id,
+            reason,
+        };
+        MIGRATION_PROPOSALS.save(deps.storage, id, &approval)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "propose_migration")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("proposal_id", id.to_string())
+            .add_attribute("new_code_id", new_code_id.to_string())
+            .add_attribute("status", format!("{:?}", prop.status));
+
+        Ok(resp)
+    }
+
+    fn open_proposal(
+        deps: DepsMut,
+        env: &Env,
+        proposer: &Addr,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg>,
+        latest: Option<Expiration>,
+    ) -> Result<(u64, Proposal), ContractError> {
+        if !ADMINS.has(deps.storage, proposer) {
+            return Err(ContractError::Unauthorized {
+                sender: proposer.clone(),
+            });
+        }
+
+        // Proposals can expire earlier than the max voting period, but not later
+        let max_expires = MAX_VOTING_PERIOD.load(deps.storage)?.after(&env.block);
+        let mut expires = latest.unwrap_or(max_expires);
+        match expires.partial_cmp(&max_expires) {
+            Some(Ordering::Greater) => expires = max_expires,
+            Some(_) => {}
+            None => return Err(ContractError::WrongExpiration {}),
+        }
+
+        // The proposer implicitly votes yes
+        let mut prop = Proposal {
+            title,
+            description,
+            start_height: env.block.height,
+            expires,
+            msgs,
+            status: Status::Open,
+            threshold: THRESHOLD.load(deps.storage)?,
+            total_weight: TOTAL.load(deps.storage)?,
+            votes: Votes {
+                yes: 1,
+                ..Votes::default()
+            },
+        };
+        prop.update_status(&env.block);
+
+        let id = PROPOSAL_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+        PROPOSALS.save(deps.storage, id, &prop)?;
+        let ballot = Ballot {
+            weight: 1,
+            vote: cw3::Vote::Yes,
+        };
+        BALLOTS.save(deps.storage, (id, proposer), &ballot)?;
+        queue_if_passed(deps.storage, env, id, &prop)?;
+
+        Ok((id, prop))
+    }
+
+    pub fn vote(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+        vote: cw3::Vote,
+    ) -> Result<Response, ContractError> {
+        let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        if prop.status != Status::Open {
+            return Err(ContractError::NotOpen {});
+        }
+        if prop.expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        // Only admins seated when the proposal was created can vote on it
+        let weight = MEMBERS
+            .may_load_at_height(deps.storage, &info.sender, prop.start_height)?
+            .ok_or_else(|| ContractError::Unauthorized {
+                sender: info.sender.clone(),
+            })?;
+
+        if BALLOTS.has(deps.storage, (proposal_id, &info.sender)) {
+            return Err(ContractError::AlreadyVoted { voter: info.sender });
+        }
+        BALLOTS.save(deps.storage, (proposal_id, &info.sender), &Ballot { weight, vote })?;
+
+        prop.votes.add_vote(vote, weight);
+        prop.update_status(&env.block);
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+        queue_if_passed(deps.storage, &env, proposal_id, &prop)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "vote")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("status", format!("{:?}", prop.status));
+
+        Ok(resp)
+    }
+
+    pub fn execute_proposal(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        // Voting may have passed the proposal only once it expired
+        prop.update_status(&env.block);
+        let queued = QUEUED.may_load(deps.storage, proposal_id)?;
+
+        // Migration proposals are consumed by migrating, executing them only queues the
+        // ones which passed on expiry, as no vote could
+        let migration = MIGRATION_PROPOSALS.has(deps.storage, proposal_id);
+        if migration && (prop.status != Status::Passed || queued.is_some()) {
+            return Err(ContractError::MigrationProposal {});
+        }
+        if prop.status != Status::Passed {
+            return Err(ContractError::WrongExecuteStatus {});
+        }
+
+        if TIMELOCK.load(deps.storage)? > 0 || migration {
+            match queued {
+                // Proposals which only passed on expiry get queued by the first execute call
+                None => {
+                    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+                    let executable_at = queue_if_passed(deps.storage, &env, proposal_id, &prop)?;
+
+                    let resp = Response::new()
+                        .add_attribute("action", "queue")
+                        .add_attribute("sender", info.sender.as_str())
+                        .add_attribute("proposal_id", proposal_id.to_string())
+                        .add_attribute("executable_at", executable_at.unwrap_or_default().to_string());
+                    return Ok(resp);
+                }
+                Some(executable_at) if env.block.time < executable_at => {
+                    return Err(ContractError::Timelocked { executable_at });
+                }
+                Some(_) => QUEUED.remove(deps.storage, proposal_id),
+            }
+        }
+
+        prop.status = Status::Executed;
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+        let resp = Response::new()
+            .add_messages(prop.msgs)
+            .add_attribute("action", "execute")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("proposal_id", proposal_id.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn close(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        if [Status::Executed, Status::Rejected, Status::Passed].contains(&prop.status)
+            || prop.current_status(&env.block) == Status::Passed
+        {
+            return Err(ContractError::WrongCloseStatus {});
+        }
+        if !prop.expires.is_expired(&env.block) {
+            return Err(ContractError::NotExpired {});
+        }
+
+        prop.status = Status::Rejected;
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "close")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("proposal_id", proposal_id.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn veto(
+        deps: DepsMut,
+        info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        if VETOER.load(deps.storage)?.as_ref() != Some(&info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        if !QUEUED.has(deps.storage, proposal_id) {
+            return Err(ContractError::NotQueued {});
+        }
+
+        let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        prop.status = Status::Rejected;
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+        QUEUED.remove(deps.storage, proposal_id);
+        MIGRATION_DEADLINES.remove(deps.storage, proposal_id);
+
+        let resp = Response::new()
+            .add_event(Event::new("proposal_vetoed").add_attribute("proposal_id", proposal_id.to_string()))
+            .add_attribute("action", "veto")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("proposal_id", proposal_id.to_string());
+
         Ok(resp)
     }
 
@@ -128,10 +1331,7 @@ mod exec {
         // rounds down because of unsigned integers division
         let donation_per_admin = donation / (admins.len() as u128);
 
-        let messages = admins.into_iter().map(|admin| BankMsg::Send {
-            to_address: admin.to_string(),
-            amount: coins(donation_per_admin, &denom),
-        });
+        let messages = distribute(deps.storage, admins, donation_per_admin, &denom)?;
 
         let resp = Response::new()
             .add_messages(messages)
@@ -141,35 +1341,492 @@ mod exec {
 
         Ok(resp)
     }
+
+    pub fn update_config(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        donation_denom: Option<String>,
+        verifier: Option<String>,
+    ) -> Result<Response, ContractError> {
+        // Either the owner, or the admins collectively through a passed proposal
+        let owner = OWNER.load(deps.storage)?;
+        if owner.as_ref() != Some(&info.sender) && info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        let mut event = Event::new("config_updated");
+
+        if let Some(donation_denom) = donation_denom {
+            // Bonds and accrued donations are held in the current denom
+            if BONDS.range(deps.storage, None, None, Order::Ascending).next().is_some()
+                || EXITS.range(deps.storage, None, None, Order::Ascending).next().is_some()
+            {
+                return Err(ContractError::DenomInUse {});
+            }
+            DONATION_DENOM.save(deps.storage, &donation_denom)?;
+            event = event.add_attribute("donation_denom", donation_denom);
+        }
+
+        if let Some(verifier) = verifier {
+            let verifier = deps.api.addr_validate(&verifier)?;
+            VERIFIER.save(deps.storage, &verifier)?;
+            event = event.add_attribute("verifier", verifier.as_str());
+        }
+
+        let resp = Response::new()
+            .add_event(event)
+            .add_attribute("action", "update_config")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn transfer_ownership(
+        deps: DepsMut,
+        info: MessageInfo,
+        new_owner: String,
+    ) -> Result<Response, ContractError> {
+        if OWNER.load(deps.storage)?.as_ref() != Some(&info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        PENDING_OWNER.save(deps.storage, &Some(new_owner.clone()))?;
+
+        let resp = Response::new()
+            .add_attribute("action", "transfer_ownership")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("pending_owner", new_owner.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn accept_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        if PENDING_OWNER.load(deps.storage)?.as_ref() != Some(&info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        OWNER.save(deps.storage, &Some(info.sender.clone()))?;
+        PENDING_OWNER.save(deps.storage, &None)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("owner", info.sender.as_str());
+
+        Ok(resp)
+    }
+
+    pub fn renounce_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        if OWNER.load(deps.storage)?.as_ref() != Some(&info.sender) {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+
+        OWNER.save(deps.storage, &None)?;
+        PENDING_OWNER.save(deps.storage, &None)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "renounce_ownership")
+            .add_attribute("sender", info.sender.as_str());
+
+        Ok(resp)
+    }
+}
+
+/// Queues a passed proposal for execution once the timelock ends, returning the
+/// time it becomes executable. Migration proposals are queued even without a
+/// timelock, as that is when their deadline starts.
+fn queue_if_passed(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposal_id: u64,
+    prop: &Proposal,
+) -> StdResult<Option<Timestamp>> {
+    let timelock = TIMELOCK.load(storage)?;
+    let migration = MIGRATION_PROPOSALS.has(storage, proposal_id);
+    if prop.status != Status::Passed || (timelock == 0 && !migration) {
+        return Ok(None);
+    }
+
+    let executable_at = env.block.time.plus_seconds(timelock);
+    QUEUED.save(storage, proposal_id, &executable_at)?;
+    if migration {
+        let executable = BlockInfo {
+            time: executable_at,
+            ..env.block.clone()
+        };
+        let deadline = MAX_VOTING_PERIOD.load(storage)?.after(&executable);
+        MIGRATION_DEADLINES.save(storage, proposal_id, &deadline)?;
+    }
+    Ok(Some(executable_at))
+}
+
+/// Seats `addr` as an admin, keeping the cw4 member snapshot in sync.
+fn seat_admin(storage: &mut dyn Storage, height: u64, addr: &Addr) -> StdResult<MemberDiff> {
+    ADMINS.save(storage, addr, &AdminProfile::default())?;
+    MEMBERS.save(storage, addr, &1, height)?;
+    TOTAL.update(storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+    ADMIN_COUNT.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    Ok(MemberDiff::new(addr, None, Some(1)))
 }
 
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+/// Removes `addr` from the admins along with its pending exit and bond, returning
+/// the accrued donations plus bond owed to it.
+fn unseat_admin(
+    storage: &mut dyn Storage,
+    height: u64,
+    addr: &Addr,
+) -> StdResult<(MemberDiff, Uint128)> {
+    let payout = settle_exit(storage, addr)?;
+    let diff = vacate_seat(storage, height, addr)?;
+    Ok((diff, payout))
+}
+
+/// Takes the seat of `addr` away, keeping the cw4 member snapshot in sync.
+fn vacate_seat(storage: &mut dyn Storage, height: u64, addr: &Addr) -> StdResult<MemberDiff> {
+    ADMINS.remove(storage, addr);
+    MEMBERS.remove(storage, addr, height)?;
+    TOTAL.update(storage, |total| -> StdResult<_> { Ok(total - 1) })?;
+    ADMIN_COUNT.update(storage, |count| -> StdResult<_> { Ok(count - 1) })?;
+
+    // Votes of a former admin no longer count towards slashing anyone
+    let cast: Vec<_> = SLASH_VOTES
+        .range(storage, None, None, Order::Ascending)
+        .filter(|entry| !matches!(entry, Ok((_, votes)) if !votes.contains(addr)))
+        .collect::<StdResult<_>>()?;
+    for ((target, percent), mut votes) in cast {
+        votes.retain(|voter| voter != addr);
+        if votes.is_empty() {
+            SLASH_VOTES.remove(storage, (&target, percent));
+        } else {
+            SLASH_VOTES.save(storage, (&target, percent), &votes)?;
+        }
+    }
+    Ok(MemberDiff::new(addr, Some(1), None))
+}
+
+/// Clears the pending exit and bond of `addr`, returning the accrued donations plus
+/// bond owed to it.
+fn settle_exit(storage: &mut dyn Storage, addr: &Addr) -> StdResult<Uint128> {
+    let accrued = EXITS
+        .may_load(storage, addr)?
+        .map(|exit| exit.accrued)
+        .unwrap_or_default();
+    let bond = BONDS.may_load(storage, addr)?.unwrap_or_default();
+
+    EXITS.remove(storage, addr);
+    BONDS.remove(storage, addr);
+    // Nothing is left to slash
+    let percents: Vec<_> = SLASH_VOTES
+        .prefix(addr)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for percent in percents {
+        SLASH_VOTES.remove(storage, (addr, percent));
+    }
+    Ok(accrued + bond)
+}
+
+/// Prepares a `MemberChangedHookMsg` for every registered hook.
+fn membership_hooks(storage: &dyn Storage, diffs: Vec<MemberDiff>) -> StdResult<Vec<SubMsg>> {
+    if diffs.is_empty() {
+        return Ok(vec![]);
+    }
+    let policy = HOOK_POLICY.load(storage)?;
+    let msg = MemberChangedHookMsg::new(diffs);
+    HOOKS.prepare_hooks(storage, |hook| {
+        let msg = msg.clone().into_cosmos_msg(hook)?;
+        Ok(match policy {
+            HookPolicy::Revert => SubMsg::new(msg),
+            HookPolicy::Log => SubMsg::reply_on_error(msg, MEMBER_HOOK_REPLY_ID),
+        })
+    })
+}
+
+/// Returns the times of additions made by `addr` within the current quota window.
+fn recent_additions(storage: &dyn Storage, env: &Env, addr: &Addr) -> StdResult<Vec<Timestamp>> {
+    let window = match INVITE_QUOTA.load(storage)? {
+        Some(quota) => quota.window,
+        None => return Ok(vec![]),
+    };
+    let additions = ADDITIONS
+        .may_load(storage, addr)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|time| time.plus_seconds(window) > env.block.time)
+        .collect();
+    Ok(additions)
+}
+
+/// Pays `amount` to each of `admins`. Admins with a pending exit still get their
+/// share, but it is held by the contract and settled once they complete leaving.
+fn distribute(
+    storage: &mut dyn Storage,
+    admins: Vec<Addr>,
+    amount: u128,
+    denom: &str,
+) -> StdResult<Vec<BankMsg>> {
+    let mut messages = vec![];
+    if amount == 0 {
+        return Ok(messages);
+    }
+
+    for admin in admins {
+        match EXITS.may_load(storage, &admin)? {
+            Some(mut exit) => {
+                exit.accrued += Uint128::new(amount);
+                EXITS.save(storage, &admin, &exit)?;
+            }
+            None => messages.push(BankMsg::Send {
+                to_address: admin.to_string(),
+                amount: coins(amount, denom),
+            }),
+        }
+    }
+    Ok(messages)
+}
+
+/// Appends the current cw2 version and state schema `version` to the history.
+fn record_version(storage: &mut dyn Storage, env: &Env, version: u32) -> StdResult<()> {
+    let id = VERSION_HISTORY
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(1, |id| id + 1);
+    let record = VersionRecord {
+        id,
+        contract_version: cw2::get_contract_version(storage)?,
+        schema_version: version,
+        height: env.block.height,
+        time: env.block.time,
+    };
+    VERSION_HISTORY.save(storage, id, &record)
+}
+
+/// Checks `proposal_id` is a passed `ProposeMigration` proposal for `reason`. Like
+/// other proposals it has to wait out the timelock, during which the vetoer can still
+/// reject it, and it can then be used until its deadline. The code id is only checked
+/// in `reply`, see `check_migration_code`.
+fn approved_migration(
+    deps: Deps,
+    env: &Env,
+    proposal_id: Option<u64>,
+    reason: &str,
+) -> Result<u64, ContractError> {
+    let not_approved = || ContractError::MigrationNotApproved {
+        reason: reason.to_owned(),
+    };
+
+    let id = proposal_id.ok_or_else(not_approved)?;
+    let approval = MIGRATION_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or_else(not_approved)?;
+    if approval.reason != reason {
+        return Err(not_approved());
+    }
+
+    let prop = PROPOSALS.load(deps.storage, id)?;
+    if prop.current_status(&env.block) != Status::Passed {
+        return Err(not_approved());
+    }
+
+    // Proposals which only passed on expiry are queued by `Execute`
+    let executable_at = QUEUED
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotQueued {})?;
+    if env.block.time < executable_at {
+        return Err(ContractError::Timelocked { executable_at });
+    }
+    if MIGRATION_DEADLINES.load(deps.storage, id)?.is_expired(&env.block) {
+        return Err(not_approved());
+    }
+    Ok(id)
+}
+
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Same-version migrations are allowed, as it is the state schema version bump
+    // which is validated by the verifier
+    if let Some(stored) = cw2::CONTRACT.may_load(deps.storage)? {
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::WrongContract {
+                contract: stored.contract,
+            });
+        }
+
+        let stored_version: Version = stored
+            .version
+            .parse()
+            .map_err(|err: semver::Error| ContractError::SemVer(err.to_string()))?;
+        let new_version: Version = CONTRACT_VERSION
+            .parse()
+            .map_err(|err: semver::Error| ContractError::SemVer(err.to_string()))?;
+        if stored_version > new_version {
+            return Err(ContractError::CannotDowngrade {
+                stored: stored.version,
+                new: CONTRACT_VERSION.to_owned(),
+            });
+        }
+    }
+
+    // Contracts from before proposals existed have no way to approve a migration
+    let proposal_id = match PROPOSAL_COUNT.may_load(deps.storage)? {
+        Some(_) => Some(approved_migration(deps.as_ref(), &env, msg.proposal_id, &msg.reason)?),
+        None => None,
+    };
+
+    // All steps are applied at once in the reply, the verifier only sees the full path
     let current_version = get_version(deps.as_ref())?;
+    let path = migration_path(current_version);
     let verifier_addr = VERIFIER.load(deps.storage)?;
 
+    let id = MIGRATION_HISTORY
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(1, |id| id + 1);
+    let attempt = MigrationAttempt {
+        id,
+        from_version: current_version,
+        to_version: NEW_VERSION,
+        reason: msg.reason.clone(),
+        verifier: verifier_addr.clone(),
+        proposal_id,
+        outcome: MigrationOutcome::Pending,
+        error: None,
+        validation: None,
+        height: env.block.height,
+        time: env.block.time,
+    };
+    MIGRATION_HISTORY.save(deps.storage, id, &attempt)?;
+
     let execute_msg = WasmMsg::Execute {
         contract_addr: verifier_addr.to_string(),
-        msg: to_binary(&verifier::msg::ExecuteMsg::ValidateMigrationMsg {
+        msg: to_json_binary(&verifier::msg::ExecuteMsg::ValidateMigrationMsg {
            current_version,
            new_version: NEW_VERSION,
-           reason: msg.reason, 
+           reason: msg.reason,
+           attestation: msg.attestation,
         })?,
         funds: vec![],
     };
 
-    let sub_msg = SubMsg::reply_always(execute_msg, VALIDATE_MIGRATION_REPLY_ID);
+    // Without a reply on error the verifier's rejection fails the whole migration
+    let sub_msg = match msg.on_rejection.unwrap_or(MigrationPolicy::Revert) {
+        MigrationPolicy::Revert => {
+            SubMsg::reply_on_success(execute_msg, VALIDATE_MIGRATION_REPLY_ID)
+        }
+        // A logged rejection would leave the new code on a state it cannot read
+        MigrationPolicy::Log if !path.is_empty() => {
+            return Err(ContractError::PendingSteps { version: current_version });
+        }
+        MigrationPolicy::Log => SubMsg::reply_always(execute_msg, VALIDATE_MIGRATION_REPLY_ID),
+    };
 
-    Ok(Response::new().add_submessage(sub_msg))
+    let mut resp = Response::new()
+        .add_submessage(sub_msg)
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", current_version.to_string());
+    // Empty attribute values are not allowed
+    if !path.is_empty() {
+        let path: Vec<_> = path.iter().map(u32::to_string).collect();
+        resp = resp.add_attribute("path", path.join(","));
+    }
+    Ok(resp)
 }
 
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+/// Checks the contract runs the code `proposal_id` approved. The chain stores the new
+/// code id before dispatching the submessages of `migrate`, so only replies can see it.
+fn check_migration_code(deps: Deps, env: &Env, proposal_id: u64) -> Result<(), ContractError> {
+    let approved = MIGRATION_PROPOSALS.load(deps.storage, proposal_id)?.new_code_id;
+    let code_id = deps
+        .querier
+        .query_wasm_contract_info(&env.contract.address)?
+        .code_id;
+    if code_id != approved {
+        return Err(ContractError::WrongMigrationCode { approved, code_id });
+    }
+    Ok(())
+}
+
+/// Decodes the `ValidationResult` the verifier sets as its execute response data.
+fn validation_result(data: Option<Binary>) -> StdResult<verifier::msg::ValidationResult> {
+    let data = data.ok_or_else(|| cosmwasm_std::StdError::not_found("ValidationResult"))?;
+    let resp = cw_utils::parse_execute_response_data(&data)
+        .map_err(|err| cosmwasm_std::StdError::parse_err("MsgExecuteContractResponse", err.to_string()))?;
+    let data = resp.data.ok_or_else(|| cosmwasm_std::StdError::not_found("ValidationResult"))?;
+    from_json(&data)
+}
+
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         VALIDATE_MIGRATION_REPLY_ID => {
-            if msg.result.is_ok() {
-                VERSION.save(deps.storage, &NEW_VERSION)?;
+            let (id, mut attempt) = MIGRATION_HISTORY
+                .range(deps.storage, None, None, Order::Descending)
+                .next()
+                .transpose()?
+                .ok_or_else(|| cosmwasm_std::StdError::not_found("MigrationAttempt"))?;
+            // Even a logged rejection swaps the code, so it is checked either way
+            if let Some(proposal_id) = attempt.proposal_id {
+                check_migration_code(deps.as_ref(), &env, proposal_id)?;
+            }
+
+            let resp = match msg.result.into_result() {
+                Ok(resp) => {
+                    let validation = validation_result(resp.data)?;
+                    // The state was only migrated up to the version requested
+                    if validation.approved_version != attempt.to_version {
+                        return Err(cosmwasm_std::StdError::generic_err(format!(
+                            "Verifier approved version {} instead of {}",
+                            validation.approved_version, attempt.to_version
+                        ))
+                        .into());
+                    }
+
+                    migrate_state(deps.storage, &env, attempt.from_version)?;
+                    let version = validation.approved_version;
+                    VERSION.save(deps.storage, &version)?;
+                    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+                    record_version(deps.storage, &env, version)?;
+                    if let Some(proposal_id) = attempt.proposal_id {
+                        let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+                        prop.status = Status::Executed;
+                        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+                        QUEUED.remove(deps.storage, proposal_id);
+                        MIGRATION_DEADLINES.remove(deps.storage, proposal_id);
+                    }
+                    attempt.outcome = MigrationOutcome::Succeeded;
+                    attempt.validation = Some(validation);
+                    Response::new()
+                }
+                Err(err) => {
+                    attempt.outcome = MigrationOutcome::Rejected;
+                    attempt.error = Some(err.clone());
+                    Response::new()
+                        .add_event(Event::new("migration_rejected").add_attribute("error", err))
+                }
+            };
+            MIGRATION_HISTORY.save(deps.storage, id, &attempt)?;
+            Ok(resp)
+        }
+        MEMBER_HOOK_REPLY_ID => {
+            // Hooks are only dispatched with `reply_on_error`
+            match msg.result {
+                SubMsgResult::Err(err) => Ok(Response::new()
+                    .add_event(Event::new("hook_failed").add_attribute("error", err))),
+                SubMsgResult::Ok(_) => Err(cosmwasm_std::StdError::generic_err(
+                    "Unexpected successful hook reply",
+                )
+                .into()),
             }
-            Ok(Response::new())
         }
-        _ => Err(cosmwasm_std::StdError::generic_err("Unknown reply ID")),
+        _ => Err(cosmwasm_std::StdError::generic_err("Unknown reply ID").into()),
     }
 }
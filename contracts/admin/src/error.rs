@@ -1,4 +1,6 @@
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, StdError, Timestamp, Uint128};
+use cw_controllers::HookError;
+use cw_utils::ThresholdError;
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -13,9 +15,117 @@ pub enum ContractError {
   #[error("Payment error: {0}")]
   Payment(#[from] PaymentError),
 
+  #[error("{0}")]
+  Hook(#[from] HookError),
+
+  #[error("{0}")]
+  Threshold(#[from] ThresholdError),
+
   #[error("{admin} is already an admin")]
   AdminExists { admin: Addr },
 
+  #[error("Contract requires at least {min} admins")]
+  MinAdmins { min: u32 },
+
+  #[error("Minimum of {min} admins must be between 1 and the {admins} initial admins")]
+  InvalidMinAdmins { min: u32, admins: u32 },
+
+  #[error("Leaving requires RequestLeave and a {cooldown}s cooldown")]
+  LeaveCooldown { cooldown: u64 },
+
+  #[error("{addr} already requested to leave")]
+  ExitPending { addr: Addr },
+
+  #[error("{addr} has not requested to leave")]
+  NoPendingExit { addr: Addr },
+
+  #[error("Leave cooldown ends at {ready_at}")]
+  CooldownNotFinished { ready_at: Timestamp },
+
+  #[error("{addr} is already invited")]
+  AlreadyInvited { addr: Addr },
+
+  #[error("Bonded seats require a leave cooldown")]
+  BondWithoutCooldown {},
+
+  #[error("Bonded seats cannot be handed over, the successor has to post a bond")]
+  BondedSuccession {},
+
+  #[error("{addr} has no pending invitation")]
+  NotInvited { addr: Addr },
+
+  #[error("Bond must be exactly {expected}")]
+  InvalidBond { expected: Uint128 },
+
+  #[error("{addr} has no bond to slash")]
+  NoBond { addr: Addr },
+
+  #[error("Invalid slash percent {percent}, expected 1-100")]
+  InvalidPercent { percent: u64 },
+
+  #[error("{voter} already voted")]
+  AlreadyVoted { voter: Addr },
+
+  #[error("Contract is limited to {max} admins")]
+  MaxAdmins { max: u32 },
+
+  #[error("Quota of {max_additions} additions per {window}s exceeded")]
+  QuotaExceeded { max_additions: u32, window: u64 },
+
+  #[error("Profile {field} exceeds {max} characters")]
+  ProfileFieldTooLong { field: String, max: usize },
+
+  #[error("Admin seats have a weight of 1, got {weight}")]
+  InvalidWeight { weight: u64 },
+
+  #[error("Proposal is not open")]
+  NotOpen {},
+
+  #[error("Proposal voting period has expired")]
+  Expired {},
+
+  #[error("Proposal must expire before you can close it")]
+  NotExpired {},
+
+  #[error("Proposal expiration is beyond the max voting period")]
+  WrongExpiration {},
+
+  #[error("Cannot close completed or passed proposals")]
+  WrongCloseStatus {},
+
+  #[error("Proposal must have passed and not yet been executed")]
+  WrongExecuteStatus {},
+
+  #[error("Proposal is timelocked until {executable_at}")]
+  Timelocked { executable_at: Timestamp },
+
+  #[error("Proposal is not queued")]
+  NotQueued {},
+
+  #[error("Donation denom cannot change while bonds or pending exits are held")]
+  DenomInUse {},
+
+  #[error("No approved migration proposal for reason {reason}")]
+  MigrationNotApproved { reason: String },
+
+  #[error("Migration to code {code_id} was approved for code {approved}")]
+  WrongMigrationCode { approved: u64, code_id: u64 },
+
+  #[error("Migration proposals are consumed by migrating the contract")]
+  MigrationProposal {},
+
+  #[error("Rejections cannot be logged with steps pending from version {version}")]
+  PendingSteps { version: u32 },
+
+  #[error("Cannot migrate from contract {contract}")]
+  WrongContract { contract: String },
+
+  #[error("Cannot downgrade from {stored} to {new}")]
+  CannotDowngrade { stored: String, new: String },
+
+  #[error("Invalid semver: {0}")]
+  SemVer(String),
+
   #[error("Contract already at version {version}")]
   AlreadyMigrated { version: u32 },
 
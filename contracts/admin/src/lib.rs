@@ -7,6 +7,7 @@ pub mod contract;
 pub mod msg;
 pub mod state;
 pub mod error;
+pub mod migrations;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -14,7 +15,7 @@ pub fn instantiate(
     env: Env,
     info: MessageInfo,
     msg: msg::InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     contract::instantiate(deps, env, info, msg)
 }
 
@@ -34,6 +35,6 @@ pub fn migrate(deps: DepsMut, env: Env, msg: msg::MigrateMsg) -> Result<Response
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     contract::reply(deps, env, msg)
 }
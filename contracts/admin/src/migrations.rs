@@ -0,0 +1,101 @@
+use crate::contract::{
+    DEFAULT_LEAVE_COOLDOWN, DEFAULT_MAX_VOTING_PERIOD, DEFAULT_MIN_ADMINS,
+};
+use crate::msg::HookPolicy;
+use crate::state::{
+    ADMINS, ADMIN_COUNT, BOND_AMOUNT, HOOK_POLICY, INVITE_QUOTA, LEAVE_COOLDOWN, MAX_ADMINS,
+    MAX_VOTING_PERIOD, MEMBERS, MIN_ADMINS, OWNER, PENDING_OWNER, PROPOSAL_COUNT, THRESHOLD,
+    TIMELOCK, TOTAL, VETOER,
+};
+use cosmwasm_std::{Decimal, Env, Order, StdResult, Storage, Uint128};
+
+/// Rewrites the state from one schema version to the next.
+pub type MigrationStep = fn(&mut dyn Storage, &Env) -> StdResult<()>;
+
+/// Ordered registry of migration steps, `MIGRATIONS[i]` moves the state from
+/// version `i + 1` to `i + 2`.
+pub const MIGRATIONS: &[MigrationStep] = &[v1_to_v2, v2_to_v3];
+
+pub const LATEST_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Versions the state passes through when migrating from `from` to `LATEST_VERSION`.
+pub fn migration_path(from: u32) -> Vec<u32> {
+    (from.max(1) + 1..=LATEST_VERSION).collect()
+}
+
+/// Runs every step between `from` and `LATEST_VERSION` in order, returning the
+/// versions the state passed through. Only called once the verifier accepted the
+/// migration, so a rejected one leaves the state untouched.
+pub fn migrate_state(storage: &mut dyn Storage, env: &Env, from: u32) -> StdResult<Vec<u32>> {
+    let mut path = vec![];
+    for (idx, step) in MIGRATIONS.iter().enumerate().skip(from.saturating_sub(1) as usize) {
+        step(storage, env)?;
+        path.push(idx as u32 + 2);
+    }
+    Ok(path)
+}
+
+// Instantiation records `LATEST_VERSION`, so version 1 state normally comes from the
+// first release, which only stored the admins, donation denom and verifier. Builds
+// that recorded version 1 for a full layout are still around, so existing values are
+// kept rather than overwritten.
+fn v1_to_v2(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    // Admins used to be stored as `Empty`, which decodes as a blank profile
+    let admins: Result<Vec<_>, _> = ADMINS
+        .range(storage, None, None, Order::Ascending)
+        .collect();
+    let admins = admins?;
+    for (admin, profile) in &admins {
+        ADMINS.save(storage, admin, profile)?;
+    }
+
+    if ADMIN_COUNT.may_load(storage)?.is_none() {
+        ADMIN_COUNT.save(storage, &(admins.len() as u32))?;
+    }
+    if TOTAL.may_load(storage)?.is_none() {
+        for (admin, _) in &admins {
+            MEMBERS.save(storage, admin, &1, env.block.height)?;
+        }
+        TOTAL.save(storage, &(admins.len() as u64))?;
+    }
+    Ok(())
+}
+
+fn v2_to_v3(storage: &mut dyn Storage, _env: &Env) -> StdResult<()> {
+    if MIN_ADMINS.may_load(storage)?.is_none() {
+        MIN_ADMINS.save(storage, &DEFAULT_MIN_ADMINS)?;
+    }
+    if LEAVE_COOLDOWN.may_load(storage)?.is_none() {
+        LEAVE_COOLDOWN.save(storage, &DEFAULT_LEAVE_COOLDOWN)?;
+    }
+    if BOND_AMOUNT.may_load(storage)?.is_none() {
+        BOND_AMOUNT.save(storage, &Uint128::zero())?;
+    }
+    if MAX_ADMINS.may_load(storage)?.is_none() {
+        MAX_ADMINS.save(storage, &None)?;
+    }
+    if INVITE_QUOTA.may_load(storage)?.is_none() {
+        INVITE_QUOTA.save(storage, &None)?;
+    }
+    if HOOK_POLICY.may_load(storage)?.is_none() {
+        HOOK_POLICY.save(storage, &HookPolicy::Revert)?;
+    }
+    if THRESHOLD.may_load(storage)?.is_none() {
+        let threshold = cw_utils::Threshold::AbsolutePercentage {
+            percentage: Decimal::percent(51),
+        };
+        THRESHOLD.save(storage, &threshold)?;
+        MAX_VOTING_PERIOD.save(storage, &DEFAULT_MAX_VOTING_PERIOD)?;
+        PROPOSAL_COUNT.save(storage, &0)?;
+    }
+    // There is no way to tell who deployed the contract, so it starts without an owner
+    if OWNER.may_load(storage)?.is_none() {
+        OWNER.save(storage, &None)?;
+        PENDING_OWNER.save(storage, &None)?;
+    }
+    if TIMELOCK.may_load(storage)?.is_none() {
+        TIMELOCK.save(storage, &0)?;
+        VETOER.save(storage, &None)?;
+    }
+    Ok(())
+}
@@ -1,34 +1,339 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, CosmosMsg, Timestamp, Uint128};
+use cw_utils::{Duration, Expiration, Threshold};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(AdminsListResp)]
-    AdminsList {},
+    AdminsList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(AdminInfoResp)]
+    AdminInfo { addr: String },
+    #[returns(AdminCountResp)]
+    AdminCount {},
+    #[returns(cw4::MemberResponse)]
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+    #[returns(cw4::MemberListResponse)]
+    ListMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(cw4::TotalWeightResponse)]
+    TotalWeight {},
+    #[returns(cw4::HooksResponse)]
+    Hooks {},
+    #[returns(IsAdminResp)]
+    IsAdmin { addr: String },
+    #[returns(PendingExitResp)]
+    PendingExit { addr: String },
+    #[returns(PendingExitsResp)]
+    PendingExits {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(BondResp)]
+    Bond { addr: String },
+    #[returns(QuotaResp)]
+    Quota { addr: String },
+    #[returns(cw_utils::ThresholdResponse)]
+    Threshold {},
+    #[returns(cw3::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    #[returns(cw3::ProposalListResponse)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(cw3::VoteResponse)]
+    Vote { proposal_id: u64, voter: String },
+    #[returns(cw3::VoteListResponse)]
+    ListVotes {
+        proposal_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ProposalQueueResp)]
+    ProposalQueue {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(ConfigResp)]
+    Config {},
+    #[returns(cw2::ContractVersion)]
+    ContractVersion {},
+    #[returns(VersionHistoryResp)]
+    VersionHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(MigrationHistoryResp)]
+    MigrationHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(LastMigrationResp)]
+    LastMigration {},
+    /// Asks the verifier whether a migration would be accepted, without running it
+    #[returns(SimulateMigrationResp)]
+    SimulateMigration {
+        reason: String,
+        /// Defaults to the latest version
+        target_version: Option<u32>,
+        attestation: Option<verifier::msg::Attestation>,
+    },
     #[returns(u32)]
     GetVersion {},
 }
 #[cw_serde]
 pub struct InstantiateMsg {
+  pub owner: Option<String>,
   pub admins: Vec<String>,
   pub donation_denom: String,
   pub verifier: String,
+  pub min_admins: Option<u32>,
+  pub leave_cooldown: Option<u64>,
+  pub bond: Option<Uint128>,
+  pub max_admins: Option<u32>,
+  pub invite_quota: Option<InviteQuota>,
+  pub hook_policy: Option<HookPolicy>,
+  pub threshold: Option<Threshold>,
+  pub max_voting_period: Option<Duration>,
+  pub timelock: Option<u64>,
+  pub vetoer: Option<String>,
+}
+
+/// What happens to a membership change when one of the hooks fails
+#[cw_serde]
+#[derive(Copy)]
+pub enum HookPolicy {
+  /// The whole change is reverted
+  Revert,
+  /// The failure is reported in a `hook_failed` event and the change goes through
+  Log,
+}
+
+#[cw_serde]
+pub struct InviteQuota {
+  pub max_additions: u32,
+  pub window: u64,
 }
 
 #[cw_serde]
 pub struct AdminsListResp {
   pub admins: Vec<Addr>,
+  /// Profiles of `admins`, in the same order
+  pub profiles: Vec<AdminProfile>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AdminProfile {
+  pub display_name: Option<String>,
+  pub avatar_url: Option<String>,
+  pub contact: Option<String>,
+  pub bio: Option<String>,
+}
+
+#[cw_serde]
+pub struct AdminInfoResp {
+  pub addr: Addr,
+  pub profile: AdminProfile,
+}
+
+#[cw_serde]
+pub struct AdminCountResp {
+  pub count: u32,
+}
+
+#[cw_serde]
+pub struct IsAdminResp {
+  pub is_admin: bool,
+}
+
+#[cw_serde]
+pub struct PendingExit {
+  pub addr: Addr,
+  pub ready_at: Timestamp,
+  pub accrued: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingExitResp {
+  pub exit: Option<PendingExit>,
+}
+
+#[cw_serde]
+pub struct PendingExitsResp {
+  pub exits: Vec<PendingExit>,
+}
+
+#[cw_serde]
+pub struct BondResp {
+  pub amount: Uint128,
+  pub invited: bool,
+}
+
+#[cw_serde]
+pub struct QuotaResp {
+  pub used: u32,
+  pub remaining: Option<u32>,
+}
+
+#[cw_serde]
+pub struct QueuedProposal {
+  pub proposal_id: u64,
+  pub executable_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct ProposalQueueResp {
+  pub proposals: Vec<QueuedProposal>,
+}
+
+#[cw_serde]
+pub struct ConfigResp {
+  pub owner: Option<Addr>,
+  pub pending_owner: Option<Addr>,
+  pub donation_denom: String,
+  pub verifier: Addr,
+  pub min_admins: u32,
+  pub leave_cooldown: u64,
+  pub bond: Uint128,
+  pub max_admins: Option<u32>,
+  pub invite_quota: Option<InviteQuota>,
+  pub hook_policy: HookPolicy,
+  pub threshold: Threshold,
+  pub max_voting_period: Duration,
+  pub timelock: u64,
+  pub vetoer: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct VersionRecord {
+  pub id: u64,
+  pub contract_version: cw2::ContractVersion,
+  pub schema_version: u32,
+  pub height: u64,
+  pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct VersionHistoryResp {
+  pub history: Vec<VersionRecord>,
+}
+
+#[cw_serde]
+pub enum MigrationOutcome {
+  /// The verifier has not replied yet
+  Pending,
+  Succeeded,
+  Rejected,
+}
+
+#[cw_serde]
+pub struct MigrationAttempt {
+  pub id: u64,
+  pub from_version: u32,
+  pub to_version: u32,
+  pub reason: String,
+  pub verifier: Addr,
+  /// Approved migration proposal, consumed if the migration succeeds
+  pub proposal_id: Option<u64>,
+  pub outcome: MigrationOutcome,
+  pub error: Option<String>,
+  /// Verifier's verdict on a successful migration
+  pub validation: Option<verifier::msg::ValidationResult>,
+  pub height: u64,
+  pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct MigrationHistoryResp {
+  pub attempts: Vec<MigrationAttempt>,
+}
+
+#[cw_serde]
+pub struct LastMigrationResp {
+  pub attempt: Option<MigrationAttempt>,
+}
+
+#[cw_serde]
+pub struct SimulateMigrationResp {
+  pub current_version: u32,
+  pub target_version: u32,
+  pub verifier: Addr,
+  pub accepted: bool,
+  pub reasons: Vec<String>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
   AddMembers { admins: Vec<String> },
-  Leave {},
+  UpdateMembers {
+    remove: Vec<String>,
+    add: Vec<cw4::Member>,
+  },
+  AddHook { addr: String },
+  RemoveHook { addr: String },
+  Leave { successor: Option<String> },
+  RequestLeave {},
+  CompleteLeave {},
+  PostBond {},
+  Slash { addr: String, percent: u64 },
+  UpdateProfile { profile: AdminProfile },
+  Propose {
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg>,
+    latest: Option<Expiration>,
+  },
+  /// Opens a proposal which, once passed, allows migrating to `new_code_id` for `reason`
+  ProposeMigration {
+    new_code_id: u64,
+    reason: String,
+  },
+  Vote {
+    proposal_id: u64,
+    vote: cw3::Vote,
+  },
+  Execute { proposal_id: u64 },
+  Close { proposal_id: u64 },
+  Veto { proposal_id: u64 },
   Donate {},
+  UpdateConfig {
+    donation_denom: Option<String>,
+    verifier: Option<String>,
+  },
+  TransferOwnership { new_owner: String },
+  AcceptOwnership {},
+  RenounceOwnership {},
+}
+
+/// What happens to a migration when the verifier rejects it
+#[cw_serde]
+#[derive(Copy)]
+pub enum MigrationPolicy {
+  /// The whole migration is reverted, including the code swap
+  Revert,
+  /// The rejection is reported in a `migration_rejected` event and the version is left as is.
+  /// The new code keeps the old state, so it is only allowed when no steps are pending
+  Log,
 }
 
 #[cw_serde]
 pub struct MigrateMsg {
-  pub reason: String
+  pub reason: String,
+  /// Passed `ProposeMigration` proposal approving the migration, required once the
+  /// contract has proposals
+  pub proposal_id: Option<u64>,
+  /// Defaults to `Revert`
+  pub on_rejection: Option<MigrationPolicy>,
+  /// Passed on to the verifier
+  pub attestation: Option<verifier::msg::Attestation>,
 }
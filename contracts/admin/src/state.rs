@@ -1,7 +1,157 @@
-use cosmwasm_std::{Addr, Empty};
-use cw_storage_plus::{Map, Item};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, Empty, Timestamp, Uint128};
+use cw3::{Status, Vote};
+use cw_controllers::Hooks;
+use cw_storage_plus::{Map, Item, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration, Threshold};
 
-pub const ADMINS: Map<&Addr, Empty> = Map::new("admins");
+use crate::msg::{AdminProfile, HookPolicy, InviteQuota, MigrationAttempt, VersionRecord};
+
+#[cw_serde]
+pub struct Exit {
+    pub ready_at: Timestamp,
+    pub accrued: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Votes {
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+    pub veto: u64,
+}
+
+impl Votes {
+    pub fn add_vote(&mut self, vote: Vote, weight: u64) {
+        match vote {
+            Vote::Yes => self.yes += weight,
+            Vote::No => self.no += weight,
+            Vote::Abstain => self.abstain += weight,
+            Vote::Veto => self.veto += weight,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.yes + self.no + self.abstain + self.veto
+    }
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub title: String,
+    pub description: String,
+    pub start_height: u64,
+    pub expires: Expiration,
+    pub msgs: Vec<CosmosMsg>,
+    pub status: Status,
+    pub threshold: Threshold,
+    pub total_weight: u64,
+    pub votes: Votes,
+}
+
+impl Proposal {
+    /// Status of the proposal taking the current block into account, as stored
+    /// `Open` proposals may already be passed or rejected.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        let mut status = self.status;
+        if status == Status::Open && self.is_passed(block) {
+            status = Status::Passed;
+        }
+        if status == Status::Open && self.expires.is_expired(block) {
+            status = Status::Rejected;
+        }
+        status
+    }
+
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+
+    // Same semantics as the cw3 reference multisigs
+    fn is_passed(&self, block: &BlockInfo) -> bool {
+        match self.threshold {
+            Threshold::AbsoluteCount { weight } => self.votes.yes >= weight,
+            Threshold::AbsolutePercentage { percentage } => {
+                let weight = self.total_weight - self.votes.abstain;
+                self.votes.yes >= votes_needed(weight, percentage)
+            }
+            Threshold::ThresholdQuorum { threshold, quorum } => {
+                if self.votes.total() < votes_needed(self.total_weight, quorum) {
+                    return false;
+                }
+                let opinions = if self.expires.is_expired(block) {
+                    self.votes.total() - self.votes.abstain
+                } else {
+                    self.total_weight - self.votes.abstain
+                };
+                self.votes.yes >= votes_needed(opinions, threshold)
+            }
+        }
+    }
+}
+
+const PRECISION_FACTOR: u128 = 1_000_000_000;
+
+/// Rounds up, so that e.g. 50% of 3 votes requires 2 votes.
+fn votes_needed(weight: u64, percentage: Decimal) -> u64 {
+    let applied = Uint128::new(PRECISION_FACTOR * weight as u128) * percentage;
+    applied.u128().div_ceil(PRECISION_FACTOR) as u64
+}
+
+#[cw_serde]
+pub struct Ballot {
+    pub weight: u64,
+    pub vote: Vote,
+}
+
+pub const ADMINS: Map<&Addr, AdminProfile> = Map::new("admins");
+pub const ADMIN_COUNT: Item<u32> = Item::new("admin_count");
+pub const MIN_ADMINS: Item<u32> = Item::new("min_admins");
+pub const LEAVE_COOLDOWN: Item<u64> = Item::new("leave_cooldown");
+pub const EXITS: Map<&Addr, Exit> = Map::new("exits");
+pub const BOND_AMOUNT: Item<Uint128> = Item::new("bond_amount");
+pub const BONDS: Map<&Addr, Uint128> = Map::new("bonds");
+pub const INVITATIONS: Map<&Addr, Empty> = Map::new("invitations");
+pub const SLASH_VOTES: Map<(&Addr, u64), Vec<Addr>> = Map::new("slash_votes");
+pub const MAX_ADMINS: Item<Option<u32>> = Item::new("max_admins");
+pub const INVITE_QUOTA: Item<Option<InviteQuota>> = Item::new("invite_quota");
+pub const ADDITIONS: Map<&Addr, Vec<Timestamp>> = Map::new("additions");
+// cw4 compatible mirror of `ADMINS`, every admin has a weight of 1
+pub const MEMBERS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    cw4::MEMBERS_KEY,
+    cw4::MEMBERS_CHECKPOINTS,
+    cw4::MEMBERS_CHANGELOG,
+    Strategy::EveryBlock,
+);
+pub const TOTAL: Item<u64> = Item::new(cw4::TOTAL_KEY);
+pub const HOOKS: Hooks = Hooks::new("cw4-hooks");
+pub const HOOK_POLICY: Item<HookPolicy> = Item::new("hook_policy");
+pub const THRESHOLD: Item<Threshold> = Item::new("threshold");
+pub const MAX_VOTING_PERIOD: Item<Duration> = Item::new("max_voting_period");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("ballots");
+pub const TIMELOCK: Item<u64> = Item::new("timelock");
+pub const VETOER: Item<Option<Addr>> = Item::new("vetoer");
+// Passed proposals waiting for their timelock, and passed migration proposals, keyed by id
+pub const QUEUED: Map<u64, Timestamp> = Map::new("queued");
+pub const OWNER: Item<Option<Addr>> = Item::new("owner");
+pub const PENDING_OWNER: Item<Option<Addr>> = Item::new("pending_owner");
 pub const DONATION_DENOM: Item<String> = Item::new("donation_denom");
 pub const VERSION: Item<u32> = Item::new("version");
+#[cw_serde]
+pub struct MigrationApproval {
+    pub new_code_id: u64,
+    pub reason: String,
+}
+
+/// Proposals opened through `ProposeMigration`, keyed by proposal id
+pub const MIGRATION_PROPOSALS: Map<u64, MigrationApproval> = Map::new("migration_proposals");
+/// Queued migration proposals can be used for a voting period after they become executable
+pub const MIGRATION_DEADLINES: Map<u64, Expiration> = Map::new("migration_deadlines");
+
+pub const VERSION_HISTORY: Map<u64, VersionRecord> = Map::new("version_history");
+/// Attempts rejected under `MigrationPolicy::Revert` are rolled back along with the migration
+pub const MIGRATION_HISTORY: Map<u64, MigrationAttempt> = Map::new("migration_history");
 pub const VERIFIER: Item<Addr> = Item::new("verifier");
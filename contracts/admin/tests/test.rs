@@ -1,6 +1,18 @@
-use cosmwasm_std::coins;
-use cosmwasm_std::Addr;
-use cw_multi_test::{App, ContractWrapper, Executor};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{coins, to_json_binary, to_json_string};
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw3::{ProposalResponse, Status, Vote, VoteListResponse};
+use cw4::{
+    HooksResponse, Member, MemberChangedHookMsg, MemberListResponse, MemberResponse,
+    TotalWeightResponse,
+};
+use cw2::ContractVersion;
+use cw_storage_plus::{Item, Map};
+use cw_multi_test::{next_block, App, AppResponse, ContractWrapper, Executor};
+use cw_utils::ThresholdResponse;
 
 use admin::contract::{
     execute as admin_execute,
@@ -11,13 +23,315 @@ use admin::contract::{
 };
 use admin::contract::{NEW_VERSION, OLD_VERSION};
 use admin::error::ContractError;
-use admin::msg::{AdminsListResp, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use admin::msg::{
+    AdminCountResp, AdminInfoResp, AdminProfile, AdminsListResp, BondResp,
+    ConfigResp, ExecuteMsg, HookPolicy, InstantiateMsg, InviteQuota, IsAdminResp, LastMigrationResp,
+    MigrateMsg, MigrationHistoryResp, MigrationOutcome, MigrationPolicy,
+    PendingExitsResp,
+    ProposalQueueResp, QueryMsg, QuotaResp, SimulateMigrationResp, VersionHistoryResp,
+};
 
 use verifier::{
     execute as verifier_execute,
     instantiate as verifier_instantiate,
+    migrate as verifier_migrate,
     query as verifier_query,
 };
+
+#[cw_serde]
+enum HookExecuteMsg {
+    MemberChangedHook(MemberChangedHookMsg),
+}
+
+fn hook_instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn hook_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: HookExecuteMsg,
+) -> StdResult<Response> {
+    let HookExecuteMsg::MemberChangedHook(msg) = msg;
+    Ok(Response::new().add_attribute("diffs", msg.diffs.len().to_string()))
+}
+
+fn failing_hook_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: HookExecuteMsg,
+) -> StdResult<Response> {
+    Err(StdError::generic_err("hook rejected"))
+}
+
+fn hook_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
+}
+
+#[cw_serde]
+struct LegacyInstantiateMsg {
+    admins: Vec<String>,
+    donation_denom: String,
+    verifier: String,
+}
+
+/// Stores the state the way the first version of the contract did.
+fn legacy_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: LegacyInstantiateMsg,
+) -> StdResult<Response> {
+    let admins: Map<&Addr, Empty> = Map::new("admins");
+    for admin in msg.admins {
+        admins.save(deps.storage, &Addr::unchecked(admin), &Empty {})?;
+    }
+    Item::<String>::new("donation_denom").save(deps.storage, &msg.donation_denom)?;
+    Item::<u32>::new("version").save(deps.storage, &OLD_VERSION)?;
+    Item::<Addr>::new("verifier").save(deps.storage, &Addr::unchecked(msg.verifier))?;
+    Ok(Response::new())
+}
+
+/// Instantiates the current contract but records the first schema version, standing
+/// in for a contract that can vote on proposals and still has migration steps to run.
+fn outdated_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let resp = admin_instantiate(deps.branch(), env, info, msg)?;
+    Item::<u32>::new("version").save(deps.storage, &OLD_VERSION)?;
+    Ok(resp)
+}
+
+/// Stores nothing but the cw2 version, as another contract would.
+fn versioned_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ContractVersion,
+) -> StdResult<Response> {
+    cw2::set_contract_version(deps.storage, msg.contract, msg.version)?;
+    Ok(Response::new())
+}
+
+/// Registers `client` with the verifier as being at `OLD_VERSION`
+fn register_client(app: &mut App, verifier: &Addr, client: &Addr) {
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier.clone(),
+        &verifier::msg::ExecuteMsg::RegisterClient {
+            contract: client.to_string(),
+            name: "admin".to_owned(),
+            allowed_reasons: vec![],
+            version: OLD_VERSION,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+/// Passes a migration proposal with the votes of both `admin1` and `admin2`
+fn approve_migration(app: &mut App, addr: &Addr, new_code_id: u64, reason: &str) -> u64 {
+    app.update_block(next_block);
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::ProposeMigration {
+                new_code_id,
+                reason: reason.to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+    let proposal_id: u64 = resp.custom_attrs(1)
+        .iter()
+        .find(|attr| attr.key == "proposal_id")
+        .unwrap()
+        .value
+        .parse()
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin2"),
+        addr.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    proposal_id
+}
+
+/// Instantiates a verifier with the default policy.
+fn default_verifier(app: &mut App) -> Addr {
+    let verifier_code = ContractWrapper::new(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query
+    );
+    let verifier_code_id = app.store_code(Box::new(verifier_code));
+
+    app.instantiate_contract(
+        verifier_code_id,
+        Addr::unchecked("owner"),
+        &verifier::msg::InstantiateMsg {
+            owner: None,
+            reasons: None,
+            max_version_jump: None,
+            signers: None,
+            announcement_delay: None,
+            maintenance_window: None,
+        },
+        &[],
+        "Verifier",
+        None,
+    )
+    .unwrap()
+}
+
+/// Instantiates the first version of the admin contract for admin1 and admin2. It
+/// answers queries with the current code, so they can be made before migrating.
+fn legacy_admin(app: &mut App, verifier: &Addr) -> Addr {
+    let legacy_code = ContractWrapper::new(hook_execute, legacy_instantiate, admin_query);
+    let legacy_code_id = app.store_code(Box::new(legacy_code));
+
+    app.instantiate_contract(
+        legacy_code_id,
+        Addr::unchecked("owner"),
+        &LegacyInstantiateMsg {
+            admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+            donation_denom: "usdc".to_owned(),
+            verifier: verifier.to_string(),
+        },
+        &[],
+        "Contract",
+        Some("contract_admin".to_owned()),
+    )
+    .unwrap()
+}
+
+/// Stores the current admin contract code, which can be migrated to.
+fn store_admin_code(app: &mut App) -> u64 {
+    let admin_code = ContractWrapper::new(admin_execute, admin_instantiate, admin_query)
+        .with_migrate(admin_migrate)
+        .with_reply(admin_reply);
+    app.store_code(Box::new(admin_code))
+}
+
+/// Instantiates a verifier with the default policy and a legacy admin contract
+/// registered with it. Returns both addresses and the id of the code the admin
+/// contract can migrate to.
+fn setup_migration(app: &mut App) -> (Addr, Addr, u64) {
+    let verifier_addr = default_verifier(app);
+    let admin_addr = legacy_admin(app, &verifier_addr);
+    register_client(app, &verifier_addr, &admin_addr);
+
+    (verifier_addr, admin_addr, store_admin_code(app))
+}
+
+/// Same as `setup_migration`, but with an outdated contract for admin1 and admin2
+/// that has proposals, instantiated with the defaults changed by `configure`.
+fn setup_proposal_migration(
+    app: &mut App,
+    configure: impl FnOnce(&mut InstantiateMsg),
+) -> (Addr, Addr, u64) {
+    let verifier_addr = default_verifier(app);
+
+    let mut msg = InstantiateMsg {
+        owner: None,
+        admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+        donation_denom: "usdc".to_owned(),
+        verifier: verifier_addr.to_string(),
+        min_admins: None,
+        leave_cooldown: None,
+        bond: None,
+        max_admins: None,
+        invite_quota: None,
+        hook_policy: None,
+        threshold: None,
+        max_voting_period: None,
+        timelock: None,
+        vetoer: None,
+    };
+    configure(&mut msg);
+
+    let admin_code = ContractWrapper::new(admin_execute, outdated_instantiate, admin_query);
+    let admin_code_id = app.store_code(Box::new(admin_code));
+    let admin_addr = app
+        .instantiate_contract(
+            admin_code_id,
+            Addr::unchecked("owner"),
+            &msg,
+            &[],
+            "Contract",
+            Some("contract_admin".to_owned()),
+        )
+        .unwrap();
+    register_client(app, &verifier_addr, &admin_addr);
+
+    (verifier_addr, admin_addr, store_admin_code(app))
+}
+
+/// Has admin1 propose `msg` to the contract itself, admin2 approve it and admin1
+/// execute it.
+fn pass_proposal(app: &mut App, addr: &Addr, msg: &ExecuteMsg) -> AppResponse {
+    app.update_block(next_block);
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                title: "Update members".to_owned(),
+                description: "Change the admin set".to_owned(),
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: addr.to_string(),
+                    msg: to_json_binary(msg).unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+                latest: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let proposal_id: u64 = resp.custom_attrs(1)
+        .iter()
+        .find(|attr| attr.key == "proposal_id")
+        .unwrap()
+        .value
+        .parse()
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin2"),
+        addr.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap()
+}
+
 #[test]
 fn instantiation() {
     let mut app = App::default();
@@ -31,9 +345,20 @@ fn instantiation() {
             code_id,
             Addr::unchecked("owner"),
             &InstantiateMsg {
+                owner: None,
                 admins: vec![],
                 donation_denom: "usdc".to_owned(),
                 verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
             },
             &[],
             "Contract",
@@ -43,19 +368,39 @@ fn instantiation() {
 
     let resp: AdminsListResp = app
         .wrap()
-        .query_wasm_smart(addr, &QueryMsg::AdminsList {})
+        .query_wasm_smart(addr, &QueryMsg::AdminsList {
+            start_after: None,
+            limit: None,
+        })
         .unwrap();
 
-    assert_eq!(resp, AdminsListResp { admins: vec![] });
+    assert_eq!(
+        resp,
+        AdminsListResp {
+            admins: vec![],
+            profiles: vec![]
+        }
+    );
 
     let addr = app
         .instantiate_contract(
             code_id,
             Addr::unchecked("owner"),
             &InstantiateMsg {
+                owner: None,
                 admins: vec!["admin1".to_owned(), "admin2".to_owned()],
                 donation_denom: "usdc".to_owned(),
                 verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
             },
             &[],
             "Contract 2",
@@ -65,23 +410,27 @@ fn instantiation() {
 
     let resp: AdminsListResp = app
         .wrap()
-        .query_wasm_smart(addr.clone(), &QueryMsg::AdminsList {})
+        .query_wasm_smart(addr.clone(), &QueryMsg::AdminsList {
+            start_after: None,
+            limit: None,
+        })
         .unwrap();
 
     assert_eq!(
         resp,
         AdminsListResp {
-            admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+            admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
+            profiles: vec![AdminProfile::default(), AdminProfile::default()]
         }
     );
 
-    // Assert version is 1
+    // Assert version is the latest one
     let resp: u32 = app
         .wrap()
         .query_wasm_smart(addr, &QueryMsg::GetVersion {})
         .unwrap();
 
-    assert_eq!(resp, 1)
+    assert_eq!(resp, NEW_VERSION)
 }
 
 #[test]
@@ -97,9 +446,20 @@ fn unauthorized() {
             code_id,
             Addr::unchecked("owner"),
             &InstantiateMsg {
+                owner: None,
                 admins: vec![],
                 donation_denom: "usdc".to_owned(),
                 verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
             },
             &[],
             "Contract",
@@ -139,9 +499,20 @@ fn add_members() {
             code_id,
             Addr::unchecked("owner"),
             &InstantiateMsg {
+                owner: None,
                 admins: vec!["owner".to_owned()],
                 donation_denom: "usdc".to_owned(),
                 verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
             },
             &[],
             "Contract",
@@ -152,7 +523,7 @@ fn add_members() {
     let resp = app
         .execute_contract(
             Addr::unchecked("owner"),
-            addr,
+            addr.clone(),
             &ExecuteMsg::AddMembers {
                 admins: vec!["user".to_owned()],
             },
@@ -194,7 +565,175 @@ fn add_members() {
             .unwrap()
             .value,
         "user"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::AddMembers {
+                admins: vec!["User2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::StdError(StdError::GenericErr { .. })
+    ));
+}
+
+#[test]
+fn admins_pagination() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp: AdminsListResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::AdminsList {
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp.admins,
+        vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+    );
+
+    let resp: AdminsListResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::AdminsList {
+                start_after: Some("admin2".to_owned()),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.admins, vec![Addr::unchecked("admin3")]);
+}
+
+#[test]
+fn membership_queries() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddMembers {
+            admins: vec!["admin3".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin2"),
+        addr.clone(),
+        &ExecuteMsg::Leave { successor: None },
+        &[],
     )
+    .unwrap();
+
+    let resp: AdminCountResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::AdminCount {})
+        .unwrap();
+
+    assert_eq!(resp, AdminCountResp { count: 2 });
+
+    let resp: IsAdminResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::IsAdmin {
+                addr: "admin3".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert!(resp.is_admin);
+
+    let resp: IsAdminResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::IsAdmin {
+                addr: "admin2".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert!(!resp.is_admin);
 }
 
 #[test]
@@ -215,9 +754,20 @@ fn donations() {
             code_id,
             Addr::unchecked("owner"),
             &InstantiateMsg {
+                owner: None,
                 admins: vec!["admin1".to_owned(), "admin2".to_owned()],
                 donation_denom: "usdc".to_owned(),
                 verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
             },
             &[],
             "Contract",
@@ -270,62 +820,16 @@ fn donations() {
 #[test]
 fn migration() {
     let mut app = App::default();
-
-    let verifier_code = ContractWrapper::new(
-        verifier_execute,
-        verifier_instantiate,
-        verifier_query
-    );
-    let verifier_code_id = app.store_code(Box::new(verifier_code));
-
-    let verifier_addr = app
-        .instantiate_contract(
-            verifier_code_id,
-            Addr::unchecked("owner"),
-            &verifier::msg::InstantiateMsg {},
-            &[],
-            "Verifier",
-            Some("contract_admin".to_owned())
-        )
-        .unwrap();
-
-    let admin_code = ContractWrapper::new(
-            admin_execute,
-            admin_instantiate,
-            admin_query
-        );
-        
-    let admin_code_id = app.store_code(Box::new(admin_code));
-
-    let admin_addr = app
-        .instantiate_contract(
-            admin_code_id,
-            Addr::unchecked("owner"),
-            &InstantiateMsg {
-                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
-                donation_denom: "usdc".to_owned(),
-                verifier: verifier_addr.to_string(),
-            },
-            &[],
-            "Contract",
-            Some("contract_admin".to_owned()),
-        )
-        .unwrap();
-
-    let new_admin_code = ContractWrapper::new(
-            admin_execute,
-            admin_instantiate,
-            admin_query
-        )
-        .with_migrate(admin_migrate)
-        .with_reply(admin_reply);
-    let new_admin_code_id = app.store_code(Box::new(new_admin_code));
+    let (_, admin_addr, new_admin_code_id) = setup_migration(&mut app);
 
     app.migrate_contract(
         Addr::unchecked("contract_admin"),
         admin_addr.clone(),
         &MigrateMsg {
             reason: "bug_fix".to_owned(),
+            proposal_id: None,
+            on_rejection: None,
+            attestation: None,
         },
         new_admin_code_id,
     ).unwrap();
@@ -339,69 +843,195 @@ fn migration() {
 }
 
 #[test]
-fn failing_migration() {
+fn contract_versions() {
     let mut app = App::default();
+    let (verifier_addr, admin_addr, new_admin_code_id) = setup_migration(&mut app);
 
-    let verifier_code = ContractWrapper::new(
-        verifier_execute,
-        verifier_instantiate,
-        verifier_query
-    );
-    let verifier_code_id = app.store_code(Box::new(verifier_code));
-
-    let verifier_addr = app
-        .instantiate_contract(
-            verifier_code_id,
-            Addr::unchecked("owner"),
-            &verifier::msg::InstantiateMsg {},
-            &[],
-            "Verifier",
-            Some("contract_admin".to_owned())
-        )
-        .unwrap();
-
-    let admin_code = ContractWrapper::new(
-            admin_execute,
-            admin_instantiate,
-            admin_query
-        );
-        
-    let admin_code_id = app.store_code(Box::new(admin_code));
+    let contract_version = ContractVersion {
+        contract: "crates.io:admin".to_owned(),
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+    };
 
-    let admin_addr = app
+    // Fresh contracts start at the latest schema version
+    let addr = app
         .instantiate_contract(
-            admin_code_id,
+            new_admin_code_id,
             Addr::unchecked("owner"),
             &InstantiateMsg {
-                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                owner: None,
+                admins: vec!["admin1".to_owned()],
                 donation_denom: "usdc".to_owned(),
                 verifier: verifier_addr.to_string(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
             },
             &[],
             "Contract",
-            Some("contract_admin".to_owned()),
+            None,
         )
         .unwrap();
 
-    let new_admin_code = ContractWrapper::new(
-            admin_execute,
-            admin_instantiate,
-            admin_query
+    let resp: ContractVersion = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::ContractVersion {})
+        .unwrap();
+
+    assert_eq!(resp, contract_version);
+
+    let resp: VersionHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::VersionHistory {
+                start_after: None,
+                limit: None,
+            },
         )
-        .with_migrate(admin_migrate)
-        .with_reply(admin_reply);
-    let new_admin_code_id = app.store_code(Box::new(new_admin_code));
+        .unwrap();
+
+    let versions: Vec<_> = resp
+        .history
+        .iter()
+        .map(|record| (record.id, record.contract_version.clone(), record.schema_version))
+        .collect();
+    assert_eq!(versions, vec![(1, contract_version.clone(), NEW_VERSION)]);
+
+    // The first version of the contract kept neither the cw2 version nor a history
+    app.wrap()
+        .query_wasm_smart::<ContractVersion>(admin_addr.clone(), &QueryMsg::ContractVersion {})
+        .unwrap_err();
+
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &MigrateMsg {
+            reason: "bug_fix".to_owned(),
+            proposal_id: None,
+            on_rejection: None,
+            attestation: None,
+        },
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    let resp: ContractVersion = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::ContractVersion {})
+        .unwrap();
+
+    assert_eq!(resp, contract_version);
+
+    let resp: VersionHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr.clone(),
+            &QueryMsg::VersionHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    let versions: Vec<_> = resp
+        .history
+        .iter()
+        .map(|record| (record.id, record.contract_version.clone(), record.schema_version))
+        .collect();
+    assert_eq!(versions, vec![(1, contract_version, NEW_VERSION)]);
+
+    let resp: VersionHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr,
+            &QueryMsg::VersionHistory {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert!(resp.history.is_empty());
 
-    // We expect this call to succeed as it dispatches an `execute` call to
-    // the Verifier contract, which will only update the contract version on success.
+    // Versions are compared as semver, and only for the same contract
+    let versioned_code = ContractWrapper::new(hook_execute, versioned_instantiate, hook_query);
+    let versioned_code_id = app.store_code(Box::new(versioned_code));
+
+    let cases = [
+        (
+            ContractVersion {
+                contract: "crates.io:other".to_owned(),
+                version: "0.1.0".to_owned(),
+            },
+            ContractError::WrongContract {
+                contract: "crates.io:other".to_owned(),
+            },
+        ),
+        (
+            ContractVersion {
+                contract: "crates.io:admin".to_owned(),
+                version: "0.10.0".to_owned(),
+            },
+            ContractError::CannotDowngrade {
+                stored: "0.10.0".to_owned(),
+                new: env!("CARGO_PKG_VERSION").to_owned(),
+            },
+        ),
+    ];
+    for (stored, expected) in cases {
+        let addr = app
+            .instantiate_contract(
+                versioned_code_id,
+                Addr::unchecked("owner"),
+                &stored,
+                &[],
+                "Contract",
+                Some("contract_admin".to_owned()),
+            )
+            .unwrap();
+
+        let err = app
+            .migrate_contract(
+                Addr::unchecked("contract_admin"),
+                addr,
+                &MigrateMsg {
+                    reason: "bug_fix".to_owned(),
+                    proposal_id: None,
+                    on_rejection: None,
+                    attestation: None,
+                },
+                new_admin_code_id,
+            )
+            .unwrap_err();
+
+        assert_eq!(expected, err.downcast().unwrap());
+    }
+}
+
+#[test]
+fn failing_migration() {
+    let mut app = App::default();
+    let (_, admin_addr, new_admin_code_id) = setup_migration(&mut app);
+
+    // The Verifier's rejection reverts the whole migration by default
     app.migrate_contract(
         Addr::unchecked("contract_admin"),
         admin_addr.clone(),
         &MigrateMsg {
             reason: "not_bug_fix".to_owned(),
+            proposal_id: None,
+            on_rejection: None,
+            attestation: None,
         },
         new_admin_code_id,
-    ).unwrap();
+    ).unwrap_err();
 
     let version: u32 = app
         .wrap()
@@ -410,3 +1040,2965 @@ fn failing_migration() {
 
     assert_eq!(version, OLD_VERSION)
 }
+
+#[test]
+fn rejected_migration_policy() {
+    let mut app = App::default();
+    let (_, admin_addr, new_admin_code_id) = setup_migration(&mut app);
+
+    let migrate_msg = |on_rejection| MigrateMsg {
+        reason: "not_bug_fix".to_owned(),
+        proposal_id: None,
+        on_rejection: Some(on_rejection),
+        attestation: None,
+    };
+
+    // A strict migration is reverted as a whole when the Verifier rejects it
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &migrate_msg(MigrationPolicy::Revert),
+            new_admin_code_id,
+        )
+        .unwrap_err();
+
+    // Every failing rule is reported
+    assert_eq!(
+        err.root_cause().downcast_ref::<verifier::error::ContractError>(),
+        Some(&verifier::error::ContractError::ValidationFailed {
+            failures: vec![verifier::msg::RuleFailure {
+                rule: "reason_allowlist".to_owned(),
+                reason: "Invalid migration reason".to_owned(),
+            }],
+        })
+    );
+
+    let resp: LastMigrationResp = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::LastMigration {})
+        .unwrap();
+
+    assert_eq!(resp.attempt, None);
+
+    // A logged rejection would leave the new code on the old state
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &migrate_msg(MigrationPolicy::Log),
+            new_admin_code_id,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::PendingSteps {
+            version: OLD_VERSION
+        },
+        err.downcast().unwrap()
+    );
+
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &MigrateMsg {
+            reason: "bug_fix".to_owned(),
+            proposal_id: None,
+            on_rejection: None,
+            attestation: None,
+        },
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    // Without steps pending a logged rejection keeps the new code and reports the rejection
+    let proposal_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "not_bug_fix");
+    let resp = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &MigrateMsg {
+                proposal_id: Some(proposal_id),
+                ..migrate_msg(MigrationPolicy::Log)
+            },
+            new_admin_code_id,
+        )
+        .unwrap();
+
+    let rejected = resp
+        .events
+        .iter()
+        .find(|ev| ev.ty == "wasm-migration_rejected")
+        .unwrap();
+    assert!(rejected.attributes.iter().any(|attr| attr.key == "error"));
+
+    let version: u32 = app
+        .wrap()
+        .query_wasm_smart(admin_addr, &QueryMsg::GetVersion {})
+        .unwrap();
+
+    assert_eq!(version, NEW_VERSION);
+}
+
+#[test]
+fn migration_history() {
+    let mut app = App::default();
+    let (verifier_addr, admin_addr, new_admin_code_id) = setup_migration(&mut app);
+
+    let resp: LastMigrationResp = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::LastMigration {})
+        .unwrap();
+
+    assert_eq!(resp.attempt, None);
+
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &MigrateMsg {
+            reason: "bug_fix".to_owned(),
+            proposal_id: None,
+            on_rejection: None,
+            attestation: None,
+        },
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    // Rejections can only be logged once no steps are left, which also means the
+    // admins have to approve the attempt
+    let proposal_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "not_bug_fix");
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &MigrateMsg {
+            reason: "not_bug_fix".to_owned(),
+            proposal_id: Some(proposal_id),
+            on_rejection: Some(MigrationPolicy::Log),
+            attestation: None,
+        },
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    let resp: MigrationHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr.clone(),
+            &QueryMsg::MigrationHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.attempts.len(), 2);
+    let succeeded = &resp.attempts[0];
+    assert_eq!(succeeded.id, 1);
+    assert_eq!(succeeded.reason, "bug_fix");
+    assert_eq!(succeeded.outcome, MigrationOutcome::Succeeded);
+    assert_eq!(succeeded.verifier, verifier_addr);
+    assert_eq!(succeeded.from_version, OLD_VERSION);
+    assert_eq!(succeeded.to_version, NEW_VERSION);
+    assert_eq!(succeeded.error, None);
+    assert_eq!(
+        succeeded.validation,
+        Some(verifier::msg::ValidationResult {
+            approved_version: NEW_VERSION,
+            policy_id: 1,
+            warnings: vec![
+                format!("Jumps {} versions at once", NEW_VERSION - OLD_VERSION),
+                "No signer approvals required".to_owned(),
+                "No attestation required".to_owned(),
+            ],
+        })
+    );
+
+    let resp: MigrationHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr.clone(),
+            &QueryMsg::MigrationHistory {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.attempts.len(), 1);
+    let rejected = resp.attempts[0].clone();
+
+    let resp: LastMigrationResp = app
+        .wrap()
+        .query_wasm_smart(admin_addr, &QueryMsg::LastMigration {})
+        .unwrap();
+
+    let attempt = resp.attempt.unwrap();
+    assert_eq!(attempt, rejected);
+    assert_eq!(attempt.id, 2);
+    assert_eq!(attempt.reason, "not_bug_fix");
+    assert_eq!(attempt.outcome, MigrationOutcome::Rejected);
+    assert_eq!(attempt.from_version, NEW_VERSION);
+    assert_eq!(attempt.to_version, NEW_VERSION);
+    assert!(attempt.error.is_some());
+    assert_eq!(attempt.validation, None);
+}
+
+#[test]
+fn simulate_migration() {
+    let mut app = App::default();
+    let (verifier_addr, admin_addr, _) = setup_migration(&mut app);
+
+    let resp: SimulateMigrationResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr.clone(),
+            &QueryMsg::SimulateMigration {
+                reason: "bug_fix".to_owned(),
+                target_version: None,
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        SimulateMigrationResp {
+            current_version: OLD_VERSION,
+            target_version: NEW_VERSION,
+            verifier: verifier_addr,
+            accepted: true,
+            reasons: vec![],
+        }
+    );
+
+    let resp: SimulateMigrationResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr.clone(),
+            &QueryMsg::SimulateMigration {
+                reason: "not_bug_fix".to_owned(),
+                target_version: Some(OLD_VERSION),
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+    assert!(!resp.accepted);
+    assert_eq!(
+        resp.reasons,
+        vec![
+            format!("Contract already at version {}", OLD_VERSION),
+            "Invalid migration reason".to_owned(),
+        ]
+    );
+
+    // The verifier cannot know which versions this code has steps for
+    let resp: SimulateMigrationResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr,
+            &QueryMsg::SimulateMigration {
+                reason: "bug_fix".to_owned(),
+                target_version: Some(NEW_VERSION + 1),
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+    assert!(!resp.accepted);
+    assert_eq!(
+        resp.reasons,
+        vec![format!("No migration steps to version {}", NEW_VERSION + 1)]
+    );
+}
+
+#[test]
+fn migration_proposals() {
+    let mut app = App::default();
+    let (_, admin_addr, new_admin_code_id) = setup_proposal_migration(&mut app, |_| ());
+
+    let migrate = |app: &mut App, proposal_id| {
+        app.migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &MigrateMsg {
+                reason: "bug_fix".to_owned(),
+                proposal_id,
+                on_rejection: None,
+                attestation: None,
+            },
+            new_admin_code_id,
+        )
+    };
+    let not_approved = ContractError::MigrationNotApproved {
+        reason: "bug_fix".to_owned(),
+    };
+
+    // Migrating needs the admins' approval for the exact reason
+    let other_reason = approve_migration(&mut app, &admin_addr, new_admin_code_id, "other_reason");
+
+    for proposal_id in [None, Some(other_reason)] {
+        let err = migrate(&mut app, proposal_id).unwrap_err();
+        assert_eq!(not_approved, err.downcast().unwrap());
+    }
+
+    // and for the code being migrated to
+    let other_code = approve_migration(&mut app, &admin_addr, new_admin_code_id + 1, "bug_fix");
+
+    let err = migrate(&mut app, Some(other_code)).unwrap_err();
+    assert_eq!(
+        ContractError::WrongMigrationCode {
+            approved: new_admin_code_id + 1,
+            code_id: new_admin_code_id,
+        },
+        err.downcast().unwrap()
+    );
+
+    let proposal_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "bug_fix");
+
+    // Migration proposals are only consumed by migrating
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            admin_addr.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(ContractError::MigrationProposal {}, err.downcast().unwrap());
+
+    migrate(&mut app, Some(proposal_id)).unwrap();
+
+    let resp: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::Proposal { proposal_id })
+        .unwrap();
+    assert_eq!(resp.status, Status::Executed);
+
+    let err = migrate(&mut app, Some(proposal_id)).unwrap_err();
+    assert_eq!(not_approved, err.downcast().unwrap());
+
+    // Approvals expire a voting period after they pass
+    let proposal_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "bug_fix");
+    app.update_block(|block| block.time = block.time.plus_seconds(7 * 24 * 60 * 60));
+
+    let err = migrate(&mut app, Some(proposal_id)).unwrap_err();
+    assert_eq!(not_approved, err.downcast().unwrap());
+}
+
+#[test]
+fn verifier_records_migration() {
+    let mut app = App::default();
+    let (verifier_addr, admin_addr, new_admin_code_id) = setup_migration(&mut app);
+
+    for reason in ["not_bug_fix", "bug_fix"] {
+        let res = app.migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &MigrateMsg {
+                reason: reason.to_owned(),
+                proposal_id: None,
+                on_rejection: None,
+                attestation: None,
+            },
+            new_admin_code_id,
+        );
+        assert_eq!(res.is_ok(), reason == "bug_fix");
+    }
+
+    // Rejected validations are reverted along with the request
+    let resp: verifier::msg::ValidationHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            verifier_addr.clone(),
+            &verifier::msg::QueryMsg::ValidationHistory {
+                contract: admin_addr.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.validations.len(), 1);
+    assert_eq!(resp.validations[0].id, 1);
+    assert_eq!(resp.validations[0].current_version, OLD_VERSION);
+    assert_eq!(resp.validations[0].new_version, NEW_VERSION);
+    assert_eq!(resp.validations[0].reason, "bug_fix");
+
+    let resp: verifier::msg::ClientResp = app
+        .wrap()
+        .query_wasm_smart(
+            verifier_addr,
+            &verifier::msg::QueryMsg::Client {
+                contract: admin_addr.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.client.unwrap().version, NEW_VERSION);
+}
+
+#[test]
+fn legacy_state_migration() {
+    let mut app = App::default();
+    let (_, admin_addr, admin_code_id) = setup_migration(&mut app);
+
+    // The new code never runs on the legacy state, not even after a logged rejection
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &MigrateMsg {
+                reason: "not_bug_fix".to_owned(),
+                proposal_id: None,
+                on_rejection: Some(MigrationPolicy::Log),
+                attestation: None,
+            },
+            admin_code_id,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::PendingSteps {
+            version: OLD_VERSION
+        },
+        err.downcast().unwrap()
+    );
+
+    let resp = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &MigrateMsg {
+                reason: "bug_fix".to_owned(),
+                proposal_id: None,
+                on_rejection: None,
+                attestation: None,
+            },
+            admin_code_id,
+        )
+        .unwrap();
+
+    // Every step from the stored version is run in order
+    let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+    let path = wasm
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "path")
+        .unwrap();
+    let expected: Vec<_> = (OLD_VERSION + 1..=NEW_VERSION).map(|v| v.to_string()).collect();
+    assert_eq!(path.value, expected.join(","));
+
+    let version: u32 = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::GetVersion {})
+        .unwrap();
+
+    assert_eq!(version, NEW_VERSION);
+
+    let resp: AdminCountResp = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::AdminCount {})
+        .unwrap();
+
+    assert_eq!(resp, AdminCountResp { count: 2 });
+
+    let resp: TotalWeightResponse = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::TotalWeight {})
+        .unwrap();
+
+    assert_eq!(resp.weight, 2);
+
+    let resp: AdminsListResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr,
+            &QueryMsg::AdminsList {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        AdminsListResp {
+            admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
+            profiles: vec![AdminProfile::default(), AdminProfile::default()],
+        }
+    );
+}
+
+#[test]
+fn verifier_policy() {
+    let mut app = App::default();
+
+    let verifier_code = ContractWrapper::new(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query
+    );
+    let verifier_code_id = app.store_code(Box::new(verifier_code));
+
+    let verifier_addr = app
+        .instantiate_contract(
+            verifier_code_id,
+            Addr::unchecked("owner"),
+            &verifier::msg::InstantiateMsg {
+                owner: None,
+                reasons: None,
+                max_version_jump: Some(1),
+                signers: None,
+                announcement_delay: None,
+                maintenance_window: None,
+            },
+            &[],
+            "Verifier",
+            None,
+        )
+        .unwrap();
+
+    let admin_addr = legacy_admin(&mut app, &verifier_addr);
+
+    register_client(&mut app, &verifier_addr, &admin_addr);
+
+    let simulate = |app: &App, reason: &str| -> SimulateMigrationResp {
+        app.wrap()
+            .query_wasm_smart(
+                admin_addr.clone(),
+                &QueryMsg::SimulateMigration {
+                    reason: reason.to_owned(),
+                    target_version: None,
+                    attestation: None,
+                },
+            )
+            .unwrap()
+    };
+
+    let resp = simulate(&app, "bug_fix");
+    assert_eq!(
+        resp.reasons,
+        vec!["Migrations may advance at most 1 versions at once".to_owned()]
+    );
+
+    // Only the verifier owner can change its policy
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            verifier_addr.clone(),
+            &verifier::msg::ExecuteMsg::UpdatePolicy {
+                max_version_jump: None,
+                announcement_delay: 0,
+                maintenance_window: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        verifier::error::ContractError::Unauthorized {
+            sender: Addr::unchecked("admin1")
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::ExecuteMsg::UpdatePolicy {
+            max_version_jump: None,
+            announcement_delay: 0,
+            maintenance_window: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert!(simulate(&app, "bug_fix").accepted);
+    assert!(!simulate(&app, "security").accepted);
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::ExecuteMsg::AddReason {
+            reason: "security".to_owned(),
+            description: "Patches a vulnerability".to_owned(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("owner"),
+            verifier_addr.clone(),
+            &verifier::msg::ExecuteMsg::AddReason {
+                reason: "security".to_owned(),
+                description: "Patches a vulnerability".to_owned(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        verifier::error::ContractError::ReasonExists {
+            reason: "security".to_owned()
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::ExecuteMsg::RemoveReason {
+            reason: "bug_fix".to_owned(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert!(simulate(&app, "security").accepted);
+    assert!(!simulate(&app, "bug_fix").accepted);
+
+    let resp: verifier::msg::PolicyResp = app
+        .wrap()
+        .query_wasm_smart(verifier_addr.clone(), &verifier::msg::QueryMsg::Policy {})
+        .unwrap();
+    assert_eq!(resp.owner, Addr::unchecked("owner"));
+    // Bumped by the policy update and the two reason changes
+    assert_eq!(resp.policy_id, 4);
+    assert_eq!(resp.max_version_jump, None);
+
+    let resp: verifier::msg::AllowedReasonsResp = app
+        .wrap()
+        .query_wasm_smart(verifier_addr.clone(), &verifier::msg::QueryMsg::AllowedReasons {})
+        .unwrap();
+    assert_eq!(
+        resp.reasons,
+        vec![verifier::msg::AllowedReason {
+            reason: "security".to_owned(),
+            description: "Patches a vulnerability".to_owned(),
+        }]
+    );
+
+    // Only registered clients can migrate, from the version the verifier knows them at
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::ExecuteMsg::DeregisterClient {
+            contract: admin_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        simulate(&app, "security").reasons,
+        vec![format!("{} is not a registered client", admin_addr)]
+    );
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr,
+        &verifier::msg::ExecuteMsg::RegisterClient {
+            contract: admin_addr.to_string(),
+            name: "admin".to_owned(),
+            allowed_reasons: vec!["security".to_owned()],
+            version: NEW_VERSION,
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        simulate(&app, "security").reasons,
+        vec![format!("Client is at version {}, not {}", NEW_VERSION, OLD_VERSION)]
+    );
+}
+
+#[test]
+fn verifier_migration() {
+    let mut app = App::default();
+
+    // Verifiers used to keep no state at all
+    let legacy_code = ContractWrapper::new(hook_execute, hook_instantiate, hook_query);
+    let legacy_code_id = app.store_code(Box::new(legacy_code));
+
+    let verifier_code = ContractWrapper::new(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query
+    )
+    .with_migrate(verifier_migrate);
+    let verifier_code_id = app.store_code(Box::new(verifier_code));
+
+    let verifier_addr = app
+        .instantiate_contract(
+            legacy_code_id,
+            Addr::unchecked("deployer"),
+            &Empty {},
+            &[],
+            "Verifier",
+            Some("owner".to_owned()),
+        )
+        .unwrap();
+
+    app.migrate_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::MigrateMsg { owner: None },
+        verifier_code_id,
+    )
+    .unwrap();
+
+    let resp: verifier::msg::PolicyResp = app
+        .wrap()
+        .query_wasm_smart(verifier_addr.clone(), &verifier::msg::QueryMsg::Policy {})
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        verifier::msg::PolicyResp {
+            owner: Addr::unchecked("owner"),
+            policy_id: 1,
+            max_version_jump: None,
+            announcement_delay: 0,
+            maintenance_window: None,
+        }
+    );
+
+    let resp: verifier::msg::AllowedReasonsResp = app
+        .wrap()
+        .query_wasm_smart(verifier_addr.clone(), &verifier::msg::QueryMsg::AllowedReasons {})
+        .unwrap();
+
+    assert_eq!(
+        resp.reasons,
+        vec![verifier::msg::AllowedReason {
+            reason: "bug_fix".to_owned(),
+            description: "Fixes a bug".to_owned(),
+        }]
+    );
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::ExecuteMsg::RemoveReason {
+            reason: "bug_fix".to_owned(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Migrating a verifier that already has a policy keeps it
+    app.migrate_contract(
+        Addr::unchecked("owner"),
+        verifier_addr.clone(),
+        &verifier::msg::MigrateMsg {
+            owner: Some("other".to_owned()),
+        },
+        verifier_code_id,
+    )
+    .unwrap();
+
+    let resp: verifier::msg::PolicyResp = app
+        .wrap()
+        .query_wasm_smart(verifier_addr.clone(), &verifier::msg::QueryMsg::Policy {})
+        .unwrap();
+
+    assert_eq!(resp.owner, Addr::unchecked("owner"));
+    assert_eq!(resp.policy_id, 2);
+
+    let resp: verifier::msg::AllowedReasonsResp = app
+        .wrap()
+        .query_wasm_smart(verifier_addr, &verifier::msg::QueryMsg::AllowedReasons {})
+        .unwrap();
+
+    assert!(resp.reasons.is_empty());
+}
+
+#[test]
+fn migration_approvals() {
+    let mut app = App::default();
+
+    let verifier_code = ContractWrapper::new(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query
+    );
+    let verifier_code_id = app.store_code(Box::new(verifier_code));
+
+    let verifier_addr = app
+        .instantiate_contract(
+            verifier_code_id,
+            Addr::unchecked("owner"),
+            &verifier::msg::InstantiateMsg {
+                owner: None,
+                reasons: None,
+                max_version_jump: None,
+                signers: Some(verifier::msg::SignerSet {
+                    signers: vec![
+                        "signer1".to_owned(),
+                        "signer2".to_owned(),
+                        "signer3".to_owned(),
+                    ],
+                    threshold: 2,
+                    approval_window: Some(100),
+                }),
+                announcement_delay: None,
+                maintenance_window: None,
+            },
+            &[],
+            "Verifier",
+            None,
+        )
+        .unwrap();
+
+    // Listing a signer twice does not let it meet the threshold alone
+    let err = app
+        .execute_contract(
+            Addr::unchecked("owner"),
+            verifier_addr.clone(),
+            &verifier::msg::ExecuteMsg::UpdateSigners {
+                signers: verifier::msg::SignerSet {
+                    signers: vec!["signer1".to_owned(), "signer1".to_owned()],
+                    threshold: 2,
+                    approval_window: Some(100),
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        verifier::error::ContractError::InvalidThreshold {
+            threshold: 2,
+            signers: 1
+        },
+        err.downcast().unwrap()
+    );
+
+    let admin_addr = legacy_admin(&mut app, &verifier_addr);
+
+    register_client(&mut app, &verifier_addr, &admin_addr);
+
+    let new_admin_code = ContractWrapper::new(admin_execute, admin_instantiate, admin_query)
+        .with_migrate(admin_migrate)
+        .with_reply(admin_reply);
+    let new_admin_code_id = app.store_code(Box::new(new_admin_code));
+
+    let migrate_msg = MigrateMsg {
+        reason: "bug_fix".to_owned(),
+        proposal_id: None,
+        on_rejection: Some(MigrationPolicy::Revert),
+        attestation: None,
+    };
+
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &migrate_msg,
+            new_admin_code_id,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Migration rejected: Migration has 0 of the 2 required approvals (quorum)"
+    );
+
+    let approve = |app: &mut App, signer: &str| {
+        app.execute_contract(
+            Addr::unchecked(signer),
+            verifier_addr.clone(),
+            &verifier::msg::ExecuteMsg::ApproveMigration {
+                client: admin_addr.to_string(),
+                new_version: NEW_VERSION,
+            },
+            &[],
+        )
+    };
+
+    let err = approve(&mut app, "outsider").unwrap_err();
+    assert_eq!(
+        verifier::error::ContractError::NotSigner {
+            sender: Addr::unchecked("outsider")
+        },
+        err.downcast().unwrap()
+    );
+
+    // Approvals lapse once the window has passed
+    approve(&mut app, "signer1").unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    approve(&mut app, "signer2").unwrap();
+
+    let approvals = |app: &App| -> Vec<Addr> {
+        let resp: verifier::msg::ApprovalsResp = app
+            .wrap()
+            .query_wasm_smart(
+                verifier_addr.clone(),
+                &verifier::msg::QueryMsg::Approvals {
+                    client: admin_addr.to_string(),
+                    new_version: NEW_VERSION,
+                },
+            )
+            .unwrap();
+        resp.approvals.into_iter().map(|approval| approval.signer).collect()
+    };
+    assert_eq!(approvals(&app), vec![Addr::unchecked("signer2")]);
+
+    approve(&mut app, "signer3").unwrap();
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &migrate_msg,
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    let version: u32 = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::GetVersion {})
+        .unwrap();
+    assert_eq!(version, NEW_VERSION);
+
+    // Approvals are consumed by the migration
+    assert_eq!(approvals(&app), Vec::<Addr>::new());
+}
+
+#[test]
+fn attested_migration() {
+    let mut app = App::default();
+
+    // Unattested requests stay readable by verifiers predating attestations
+    let msg = verifier::msg::ExecuteMsg::ValidateMigrationMsg {
+        current_version: 1,
+        new_version: 2,
+        reason: "bug_fix".to_owned(),
+        attestation: None,
+    };
+    assert!(!to_json_string(&msg).unwrap().contains("attestation"));
+
+    let verifier_code = ContractWrapper::new(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query
+    );
+    let verifier_code_id = app.store_code(Box::new(verifier_code));
+
+    let verifier_addr = app
+        .instantiate_contract(
+            verifier_code_id,
+            Addr::unchecked("owner"),
+            &verifier::msg::InstantiateMsg {
+                owner: None,
+                reasons: None,
+                max_version_jump: None,
+                signers: None,
+                announcement_delay: None,
+                maintenance_window: None,
+            },
+            &[],
+            "Verifier",
+            None,
+        )
+        .unwrap();
+
+    let admin_addr = legacy_admin(&mut app, &verifier_addr);
+
+    register_client(&mut app, &verifier_addr, &admin_addr);
+
+    let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+    let verification_key = ed25519_zebra::VerificationKey::from(&signing_key);
+    let pubkey = Binary::from(<[u8; 32]>::from(verification_key).to_vec());
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        verifier_addr,
+        &verifier::msg::ExecuteMsg::AddTrustedKey {
+            pubkey: pubkey.clone(),
+            key_type: verifier::msg::KeyType::Ed25519,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // `signed_nonce` is the one covered by the signature, `nonce` the one presented
+    let attest = |signed_nonce: u64, nonce: u64| {
+        let payload = verifier::msg::AttestationPayload {
+            client: admin_addr.clone(),
+            current_version: OLD_VERSION,
+            new_version: NEW_VERSION,
+            reason: "bug_fix".to_owned(),
+            nonce: signed_nonce,
+        };
+        let digest = verifier::attestation_digest(&payload).unwrap();
+        let signature = <[u8; 64]>::from(signing_key.sign(&digest));
+        verifier::msg::Attestation {
+            pubkey: pubkey.clone(),
+            signature: Binary::from(signature.to_vec()),
+            nonce,
+        }
+    };
+
+    let simulate = |app: &App, attestation: Option<verifier::msg::Attestation>| -> Vec<String> {
+        let resp: SimulateMigrationResp = app
+            .wrap()
+            .query_wasm_smart(
+                admin_addr.clone(),
+                &QueryMsg::SimulateMigration {
+                    reason: "bug_fix".to_owned(),
+                    target_version: None,
+                    attestation,
+                },
+            )
+            .unwrap();
+        resp.reasons
+    };
+
+    assert_eq!(
+        simulate(&app, None),
+        vec!["Migration requires a signed attestation".to_owned()]
+    );
+    assert_eq!(
+        simulate(&app, Some(attest(1, 2))),
+        vec!["Attestation signature is invalid".to_owned()]
+    );
+    assert_eq!(simulate(&app, Some(attest(1, 1))), Vec::<String>::new());
+
+    let new_admin_code = ContractWrapper::new(admin_execute, admin_instantiate, admin_query)
+        .with_migrate(admin_migrate)
+        .with_reply(admin_reply);
+    let new_admin_code_id = app.store_code(Box::new(new_admin_code));
+
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &MigrateMsg {
+            reason: "bug_fix".to_owned(),
+            proposal_id: None,
+            on_rejection: Some(MigrationPolicy::Revert),
+            attestation: Some(attest(1, 1)),
+        },
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    let version: u32 = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::GetVersion {})
+        .unwrap();
+    assert_eq!(version, NEW_VERSION);
+
+    // Attestations cannot be replayed
+    assert!(simulate(&app, Some(attest(1, 1))).contains(&"Nonce 1 was already used".to_owned()));
+}
+
+#[test]
+fn announced_migration() {
+    let mut app = App::default();
+
+    let verifier_code = ContractWrapper::new(
+        verifier_execute,
+        verifier_instantiate,
+        verifier_query
+    );
+    let verifier_code_id = app.store_code(Box::new(verifier_code));
+
+    // Blocks start on a Wednesday at 02:23 UTC
+    let verifier_addr = app
+        .instantiate_contract(
+            verifier_code_id,
+            Addr::unchecked("owner"),
+            &verifier::msg::InstantiateMsg {
+                owner: None,
+                reasons: None,
+                max_version_jump: None,
+                signers: None,
+                announcement_delay: Some(3600),
+                maintenance_window: Some(verifier::msg::MaintenanceWindow {
+                    weekdays: vec![2],
+                    start_hour: 2,
+                    end_hour: 4,
+                }),
+            },
+            &[],
+            "Verifier",
+            None,
+        )
+        .unwrap();
+
+    let admin_addr = legacy_admin(&mut app, &verifier_addr);
+
+    register_client(&mut app, &verifier_addr, &admin_addr);
+
+    let simulate = |app: &App| -> Vec<String> {
+        let resp: SimulateMigrationResp = app
+            .wrap()
+            .query_wasm_smart(
+                admin_addr.clone(),
+                &QueryMsg::SimulateMigration {
+                    reason: "bug_fix".to_owned(),
+                    target_version: None,
+                    attestation: None,
+                },
+            )
+            .unwrap();
+        resp.reasons
+    };
+
+    assert_eq!(
+        simulate(&app),
+        vec![format!("Migration to version {} was not announced", NEW_VERSION)]
+    );
+
+    let announce = verifier::msg::ExecuteMsg::AnnounceMigration {
+        client: admin_addr.to_string(),
+        new_version: NEW_VERSION,
+    };
+    let err = app
+        .execute_contract(Addr::unchecked("admin1"), verifier_addr.clone(), &announce, &[])
+        .unwrap_err();
+    assert_eq!(
+        verifier::error::ContractError::Unauthorized {
+            sender: Addr::unchecked("admin1")
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(Addr::unchecked("owner"), verifier_addr, &announce, &[])
+        .unwrap();
+
+    let ready_at = app.block_info().time.plus_seconds(3600);
+    assert_eq!(
+        simulate(&app),
+        vec![format!("Announced migration cannot pass before {}", ready_at)]
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    assert_eq!(simulate(&app), Vec::<String>::new());
+
+    // The window closes at 04:00
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    assert_eq!(
+        simulate(&app),
+        vec!["Migrations are only allowed during the maintenance window".to_owned()]
+    );
+}
+
+#[test]
+fn timelocked_migration() {
+    let mut app = App::default();
+    let (_, admin_addr, new_admin_code_id) = setup_proposal_migration(&mut app, |msg| {
+        msg.timelock = Some(100);
+        msg.vetoer = Some("vetoer".to_owned());
+    });
+
+    let migrate_msg = |proposal_id| MigrateMsg {
+        reason: "bug_fix".to_owned(),
+        proposal_id: Some(proposal_id),
+        on_rejection: None,
+        attestation: None,
+    };
+
+    // The vetoer can still stop an approved migration during the timelock
+    let vetoed_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "bug_fix");
+    app.execute_contract(
+        Addr::unchecked("vetoer"),
+        admin_addr.clone(),
+        &ExecuteMsg::Veto {
+            proposal_id: vetoed_id,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &migrate_msg(vetoed_id),
+            new_admin_code_id,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::MigrationNotApproved {
+            reason: "bug_fix".to_owned(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Once executable an approval can only be used for a voting period
+    let stale_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "bug_fix");
+    app.update_block(|block| block.time = block.time.plus_seconds(100 + 7 * 24 * 60 * 60));
+
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &migrate_msg(stale_id),
+            new_admin_code_id,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::MigrationNotApproved {
+            reason: "bug_fix".to_owned(),
+        },
+        err.downcast().unwrap()
+    );
+
+    let proposal_id = approve_migration(&mut app, &admin_addr, new_admin_code_id, "bug_fix");
+    let err = app
+        .migrate_contract(
+            Addr::unchecked("contract_admin"),
+            admin_addr.clone(),
+            &migrate_msg(proposal_id),
+            new_admin_code_id,
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Timelocked { .. }
+    ));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    app.migrate_contract(
+        Addr::unchecked("contract_admin"),
+        admin_addr.clone(),
+        &migrate_msg(proposal_id),
+        new_admin_code_id,
+    )
+    .unwrap();
+
+    let version: u32 = app
+        .wrap()
+        .query_wasm_smart(admin_addr.clone(), &QueryMsg::GetVersion {})
+        .unwrap();
+
+    assert_eq!(version, NEW_VERSION);
+
+    let resp: ProposalQueueResp = app
+        .wrap()
+        .query_wasm_smart(
+            admin_addr,
+            &QueryMsg::ProposalQueue {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    // Only the unused approval is still listed, though it can no longer be used
+    let queued: Vec<_> = resp.proposals.iter().map(|prop| prop.proposal_id).collect();
+    assert_eq!(queued, vec![stale_id]);
+}
+
+#[test]
+fn migration_passed_on_expiry() {
+    for timelock in [None, Some(100)] {
+        let mut app = App::default();
+        let (_, admin_addr, new_admin_code_id) = setup_proposal_migration(&mut app, |msg| {
+            msg.threshold = Some(cw_utils::Threshold::ThresholdQuorum {
+                threshold: Decimal::percent(51),
+                quorum: Decimal::percent(50),
+            });
+            msg.timelock = timelock;
+        });
+
+        let migrate = |app: &mut App| {
+            app.migrate_contract(
+                Addr::unchecked("contract_admin"),
+                admin_addr.clone(),
+                &MigrateMsg {
+                    reason: "bug_fix".to_owned(),
+                    proposal_id: Some(1),
+                    on_rejection: None,
+                    attestation: None,
+                },
+                new_admin_code_id,
+            )
+        };
+
+        // admin1's vote alone only passes the proposal once it expires
+        app.update_block(next_block);
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            admin_addr.clone(),
+            &ExecuteMsg::ProposeMigration {
+                new_code_id: new_admin_code_id,
+                reason: "bug_fix".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(7 * 24 * 60 * 60));
+
+        let err = migrate(&mut app).unwrap_err();
+        assert_eq!(ContractError::NotQueued {}, err.downcast().unwrap());
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                admin_addr.clone(),
+                &ExecuteMsg::Execute { proposal_id: 1 },
+                &[],
+            )
+            .unwrap();
+
+        let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+        assert!(wasm
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "action" && attr.value == "queue"));
+
+        if let Some(timelock) = timelock {
+            let err = migrate(&mut app).unwrap_err();
+            assert!(matches!(
+                err.downcast().unwrap(),
+                ContractError::Timelocked { .. }
+            ));
+
+            app.update_block(|block| block.time = block.time.plus_seconds(timelock));
+        }
+
+        migrate(&mut app).unwrap();
+
+        let version: u32 = app
+            .wrap()
+            .query_wasm_smart(admin_addr, &QueryMsg::GetVersion {})
+            .unwrap();
+
+        assert_eq!(version, NEW_VERSION);
+    }
+}
+
+#[test]
+fn last_admin_cannot_leave() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::Leave { successor: None },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin2"),
+            addr,
+            &ExecuteMsg::Leave { successor: None },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(ContractError::MinAdmins { min: 1 }, err.downcast().unwrap());
+}
+
+#[test]
+fn min_admins_bounds() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let mut msg = InstantiateMsg {
+        owner: None,
+        admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+        donation_denom: "usdc".to_owned(),
+        verifier: "verifier".to_owned(),
+        min_admins: Some(0),
+        leave_cooldown: None,
+        bond: None,
+        max_admins: None,
+        invite_quota: None,
+        hook_policy: None,
+        threshold: None,
+        max_voting_period: None,
+        timelock: None,
+        vetoer: None,
+    };
+
+    let err = app
+        .instantiate_contract(code_id, Addr::unchecked("owner"), &msg, &[], "Contract", None)
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::InvalidMinAdmins { min: 0, admins: 2 },
+        err.downcast().unwrap()
+    );
+
+    msg.min_admins = Some(3);
+    let err = app
+        .instantiate_contract(code_id, Addr::unchecked("owner"), &msg, &[], "Contract", None)
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::InvalidMinAdmins { min: 3, admins: 2 },
+        err.downcast().unwrap()
+    );
+
+    msg.min_admins = Some(2);
+    app.instantiate_contract(code_id, Addr::unchecked("owner"), &msg, &[], "Contract", None)
+        .unwrap();
+}
+
+#[test]
+fn leave_with_successor() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::Leave {
+            successor: Some("admin2".to_owned()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: AdminsListResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::AdminsList {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.admins, vec![Addr::unchecked("admin2")]);
+
+    let resp: AdminCountResp = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::AdminCount {})
+        .unwrap();
+
+    assert_eq!(resp, AdminCountResp { count: 1 });
+}
+
+#[test]
+fn leave_with_cooldown() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("user"), coins(6, "usdc"))
+            .unwrap()
+    });
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: Some(100),
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Leave { successor: None },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::LeaveCooldown { cooldown: 100 },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::RequestLeave {},
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::RequestLeave {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::ExitPending {
+            addr: Addr::unchecked("admin1")
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::Donate {},
+        &coins(6, "usdc"),
+    )
+    .unwrap();
+
+    let resp: PendingExitsResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::PendingExits {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.exits.len(), 1);
+    assert_eq!(resp.exits[0].addr, Addr::unchecked("admin1"));
+    assert_eq!(resp.exits[0].accrued.u128(), 2);
+
+    let ready_at = resp.exits[0].ready_at;
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::CompleteLeave {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::CooldownNotFinished { ready_at },
+        err.downcast().unwrap()
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::CompleteLeave {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("admin1", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("admin2", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+
+    let resp: AdminCountResp = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::AdminCount {})
+        .unwrap();
+
+    assert_eq!(resp, AdminCountResp { count: 2 });
+}
+
+#[test]
+fn bonded_seats() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("user"), coins(10, "usdc"))
+            .unwrap()
+    });
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let mut msg = InstantiateMsg {
+        owner: None,
+        admins: vec![
+            "admin1".to_owned(),
+            "admin2".to_owned(),
+            "admin3".to_owned(),
+        ],
+        donation_denom: "usdc".to_owned(),
+        verifier: "verifier".to_owned(),
+        min_admins: None,
+        leave_cooldown: None,
+        bond: Some(Uint128::new(10)),
+        max_admins: None,
+        invite_quota: None,
+        hook_policy: None,
+        threshold: None,
+        max_voting_period: None,
+        timelock: None,
+        vetoer: None,
+    };
+
+    let err = app
+        .instantiate_contract(code_id, Addr::unchecked("owner"), &msg, &[], "Contract", None)
+        .unwrap_err();
+
+    assert_eq!(ContractError::BondWithoutCooldown {}, err.downcast().unwrap());
+
+    msg.leave_cooldown = Some(100);
+    let addr = app
+        .instantiate_contract(code_id, Addr::unchecked("owner"), &msg, &[], "Contract", None)
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddMembers {
+            admins: vec!["user".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: IsAdminResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::IsAdmin {
+                addr: "user".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert!(!resp.is_admin);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::PostBond {},
+            &coins(5, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::InvalidBond {
+            expected: Uint128::new(10)
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::PostBond {},
+        &coins(10, "usdc"),
+    )
+    .unwrap();
+
+    let resp: BondResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::Bond {
+                addr: "user".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        BondResp {
+            amount: Uint128::new(10),
+            invited: false
+        }
+    );
+
+    for admin in ["admin1", "admin2"] {
+        app.execute_contract(
+            Addr::unchecked(admin),
+            addr.clone(),
+            &ExecuteMsg::Slash {
+                addr: "user".to_owned(),
+                percent: 50,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // 5 slashed tokens split between 3 remaining admins
+    assert_eq!(
+        app.wrap()
+            .query_balance("admin3", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        1
+    );
+
+    // The bond can neither be handed over nor taken out before a slash vote passes
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Leave {
+                successor: Some("user2".to_owned()),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(ContractError::BondedSuccession {}, err.downcast().unwrap());
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Leave { successor: None },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::LeaveCooldown { cooldown: 100 },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::RequestLeave {},
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr,
+        &ExecuteMsg::CompleteLeave {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        5
+    );
+}
+
+#[test]
+fn admin_limits() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: Some(4),
+                invite_quota: Some(InviteQuota {
+                    max_additions: 1,
+                    window: 100,
+                }),
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddMembers {
+            admins: vec!["user1".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["user2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::QuotaExceeded {
+            max_additions: 1,
+            window: 100
+        },
+        err.downcast().unwrap()
+    );
+
+    let resp: QuotaResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::Quota {
+                addr: "admin1".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        QuotaResp {
+            used: 1,
+            remaining: Some(0)
+        }
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddMembers {
+            admins: vec!["user2".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin2"),
+            addr,
+            &ExecuteMsg::AddMembers {
+                admins: vec!["user3".to_owned()],
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(ContractError::MaxAdmins { max: 4 }, err.downcast().unwrap());
+}
+
+#[test]
+fn admin_profiles() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let profile = AdminProfile {
+        display_name: Some("Admin One".to_owned()),
+        avatar_url: None,
+        contact: Some("admin1@example.com".to_owned()),
+        bio: None,
+    };
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::UpdateProfile {
+                profile: profile.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert!(resp
+        .events
+        .iter()
+        .any(|ev| ev.ty == "wasm-profile_updated"));
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin2"),
+            addr.clone(),
+            &ExecuteMsg::UpdateProfile {
+                profile: AdminProfile {
+                    display_name: Some("a".repeat(65)),
+                    ..AdminProfile::default()
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::ProfileFieldTooLong {
+            field: "display_name".to_owned(),
+            max: 64
+        },
+        err.downcast().unwrap()
+    );
+
+    let resp: AdminInfoResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::AdminInfo {
+                addr: "admin1".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        AdminInfoResp {
+            addr: Addr::unchecked("admin1"),
+            profile: profile.clone()
+        }
+    );
+
+    let resp: AdminsListResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::AdminsList {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        AdminsListResp {
+            admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
+            profiles: vec![profile, AdminProfile::default()],
+        }
+    );
+}
+
+#[test]
+fn cw4_group() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let hook_code = ContractWrapper::new(hook_execute, hook_instantiate, hook_query);
+    let hook_code_id = app.store_code(Box::new(hook_code));
+
+    let hook_addr = app
+        .instantiate_contract(
+            hook_code_id,
+            Addr::unchecked("owner"),
+            &Empty {},
+            &[],
+            "Hook",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddHook {
+            addr: hook_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::Hooks {})
+        .unwrap();
+
+    assert_eq!(resp.hooks, vec![hook_addr.to_string()]);
+
+    let update_members = ExecuteMsg::UpdateMembers {
+        remove: vec!["admin2".to_owned()],
+        add: vec![Member {
+            addr: "admin3".to_owned(),
+            weight: 1,
+        }],
+    };
+
+    let err = app
+        .execute_contract(Addr::unchecked("admin1"), addr.clone(), &update_members, &[])
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Unauthorized {
+            sender: Addr::unchecked("admin1")
+        },
+        err.downcast().unwrap()
+    );
+
+    let resp = pass_proposal(&mut app, &addr, &update_members);
+
+    let hook_calls: Vec<_> = resp
+        .events
+        .iter()
+        .filter(|ev| {
+            ev.ty == "wasm"
+                && ev
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "_contract_addr" && attr.value == hook_addr.as_str())
+        })
+        .collect();
+
+    assert_eq!(hook_calls.len(), 2);
+
+    let resp: TotalWeightResponse = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::TotalWeight {})
+        .unwrap();
+
+    assert_eq!(resp.weight, 2);
+
+    let resp: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::Member {
+                addr: "admin2".to_owned(),
+                at_height: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.weight, None);
+
+    let resp: MemberListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::ListMembers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp.members,
+        vec![
+            Member {
+                addr: "admin1".to_owned(),
+                weight: 1
+            },
+            Member {
+                addr: "admin3".to_owned(),
+                weight: 1
+            }
+        ]
+    );
+}
+
+#[test]
+fn removed_admin_keeps_bond_until_exit() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("user"), coins(10, "usdc"))
+            .unwrap()
+    });
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: Some(100),
+                bond: Some(Uint128::new(10)),
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddMembers {
+            admins: vec!["user".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::PostBond {},
+        &coins(10, "usdc"),
+    )
+    .unwrap();
+
+    // Admins cannot remove themselves to get their bond back
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::UpdateMembers {
+                remove: vec!["user".to_owned()],
+                add: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Unauthorized {
+            sender: Addr::unchecked("user")
+        },
+        err.downcast().unwrap()
+    );
+
+    pass_proposal(
+        &mut app,
+        &addr,
+        &ExecuteMsg::UpdateMembers {
+            remove: vec!["user".to_owned()],
+            add: vec![],
+        },
+    );
+
+    let resp: IsAdminResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::IsAdmin {
+                addr: "user".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert!(!resp.is_admin);
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CompleteLeave {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::CooldownNotFinished { .. }
+    ));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::CompleteLeave {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        10
+    );
+
+    let resp: BondResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::Bond {
+                addr: "user".to_owned(),
+            },
+        )
+        .unwrap();
+
+    assert!(resp.amount.is_zero());
+}
+
+#[test]
+fn stale_slash_votes() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("user"), coins(10, "usdc"))
+            .unwrap()
+    });
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                    "admin4".to_owned(),
+                ],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: Some(100),
+                bond: Some(Uint128::new(10)),
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: Some(cw_utils::Threshold::AbsoluteCount { weight: 2 }),
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::AddMembers {
+            admins: vec!["user".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::PostBond {},
+        &coins(10, "usdc"),
+    )
+    .unwrap();
+
+    let slash = ExecuteMsg::Slash {
+        addr: "user".to_owned(),
+        percent: 50,
+    };
+    app.execute_contract(Addr::unchecked("admin4"), addr.clone(), &slash, &[])
+        .unwrap();
+
+    pass_proposal(
+        &mut app,
+        &addr,
+        &ExecuteMsg::UpdateMembers {
+            remove: vec!["admin4".to_owned()],
+            add: vec![],
+        },
+    );
+
+    // The vote of the removed admin does not make up the majority of the other 3
+    app.execute_contract(Addr::unchecked("admin1"), addr.clone(), &slash, &[])
+        .unwrap();
+
+    let bond_query = QueryMsg::Bond {
+        addr: "user".to_owned(),
+    };
+    let resp: BondResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &bond_query)
+        .unwrap();
+
+    assert_eq!(resp.amount, Uint128::new(10));
+
+    app.execute_contract(Addr::unchecked("admin2"), addr.clone(), &slash, &[])
+        .unwrap();
+
+    let resp: BondResp = app
+        .wrap()
+        .query_wasm_smart(addr, &bond_query)
+        .unwrap();
+
+    assert_eq!(resp.amount, Uint128::new(5));
+}
+
+#[test]
+fn failing_hook_policy() {
+    let mut app = App::default();
+
+    let code = ContractWrapper::new(admin_execute, admin_instantiate, admin_query)
+        .with_reply(admin_reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let hook_code = ContractWrapper::new(failing_hook_execute, hook_instantiate, hook_query);
+    let hook_code_id = app.store_code(Box::new(hook_code));
+
+    let hook_addr = app
+        .instantiate_contract(
+            hook_code_id,
+            Addr::unchecked("owner"),
+            &Empty {},
+            &[],
+            "Hook",
+            None,
+        )
+        .unwrap();
+
+    let mut contracts = vec![];
+    for policy in [HookPolicy::Revert, HookPolicy::Log] {
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    owner: None,
+                    admins: vec!["admin1".to_owned()],
+                    donation_denom: "usdc".to_owned(),
+                    verifier: "verifier".to_owned(),
+                    min_admins: None,
+                    leave_cooldown: None,
+                    bond: None,
+                    max_admins: None,
+                    invite_quota: None,
+                    hook_policy: Some(policy),
+                    threshold: None,
+                    max_voting_period: None,
+                    timelock: None,
+                    vetoer: None,
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddHook {
+                addr: hook_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        contracts.push(addr);
+    }
+
+    let add_members = ExecuteMsg::AddMembers {
+        admins: vec!["user".to_owned()],
+    };
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        contracts[0].clone(),
+        &add_members,
+        &[],
+    )
+    .unwrap_err();
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            contracts[1].clone(),
+            &add_members,
+            &[],
+        )
+        .unwrap();
+
+    assert!(resp.events.iter().any(|ev| ev.ty == "wasm-hook_failed"));
+
+    let resp: AdminCountResp = app
+        .wrap()
+        .query_wasm_smart(contracts[0].clone(), &QueryMsg::AdminCount {})
+        .unwrap();
+
+    assert_eq!(resp, AdminCountResp { count: 1 });
+
+    let resp: AdminCountResp = app
+        .wrap()
+        .query_wasm_smart(contracts[1].clone(), &QueryMsg::AdminCount {})
+        .unwrap();
+
+    assert_eq!(resp, AdminCountResp { count: 2 });
+}
+
+#[test]
+fn proposals() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("user"), coins(10, "usdc"))
+            .unwrap()
+    });
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.send_tokens(Addr::unchecked("user"), addr.clone(), &coins(10, "usdc"))
+        .unwrap();
+    app.update_block(next_block);
+
+    let resp: ThresholdResponse = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::Threshold {})
+        .unwrap();
+
+    assert_eq!(
+        resp,
+        ThresholdResponse::AbsolutePercentage {
+            percentage: Decimal::percent(51),
+            total_weight: 3
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::Propose {
+            title: "Pay recipient".to_owned(),
+            description: "Spend contract funds".to_owned(),
+            msgs: vec![BankMsg::Send {
+                to_address: "recipient".to_owned(),
+                amount: coins(10, "usdc"),
+            }
+            .into()],
+            latest: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(ContractError::WrongExecuteStatus {}, err.downcast().unwrap());
+
+    app.execute_contract(
+        Addr::unchecked("admin2"),
+        addr.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+
+    assert_eq!(resp.status, Status::Passed);
+
+    let resp: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.votes.len(), 2);
+
+    app.execute_contract(
+        Addr::unchecked("admin3"),
+        addr.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("recipient", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        10
+    );
+
+    let resp: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+
+    assert_eq!(resp.status, Status::Executed);
+}
+
+#[test]
+fn timelocked_proposals() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: Some(100),
+                vetoer: Some("guardian".to_owned()),
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.update_block(next_block);
+
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                title: "Noop".to_owned(),
+                description: "Does nothing".to_owned(),
+                msgs: vec![],
+                latest: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    for proposal_id in [1, 2] {
+        app.execute_contract(
+            Addr::unchecked("admin2"),
+            addr.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let resp: ProposalQueueResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::ProposalQueue {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.proposals.len(), 2);
+    let executable_at = resp.proposals[0].executable_at;
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Timelocked { executable_at },
+        err.downcast().unwrap()
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Veto { proposal_id: 2 },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Unauthorized {
+            sender: Addr::unchecked("admin1")
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("guardian"),
+        addr.clone(),
+        &ExecuteMsg::Veto { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    app.execute_contract(
+        Addr::unchecked("admin1"),
+        addr.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    for (proposal_id, status) in [(1, Status::Executed), (2, Status::Rejected)] {
+        let resp: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+
+        assert_eq!(resp.status, status);
+    }
+}
+
+#[test]
+fn ownership_and_config() {
+    let mut app = App::default();
+
+    let code =
+        ContractWrapper::new(admin_execute, admin_instantiate, admin_query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: None,
+                admins: vec!["admin1".to_owned()],
+                donation_denom: "usdc".to_owned(),
+                verifier: "verifier".to_owned(),
+                min_admins: None,
+                leave_cooldown: None,
+                bond: None,
+                max_admins: None,
+                invite_quota: None,
+                hook_policy: None,
+                threshold: None,
+                max_voting_period: None,
+                timelock: None,
+                vetoer: None,
+            },
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let update_config = ExecuteMsg::UpdateConfig {
+        donation_denom: Some("uatom".to_owned()),
+        verifier: Some("verifier2".to_owned()),
+    };
+
+    let err = app
+        .execute_contract(Addr::unchecked("admin1"), addr.clone(), &update_config, &[])
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Unauthorized {
+            sender: Addr::unchecked("admin1")
+        },
+        err.downcast().unwrap()
+    );
+
+    let resp = app
+        .execute_contract(Addr::unchecked("owner"), addr.clone(), &update_config, &[])
+        .unwrap();
+
+    assert!(resp
+        .events
+        .iter()
+        .any(|ev| ev.ty == "wasm-config_updated"));
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &ExecuteMsg::TransferOwnership {
+            new_owner: "new_owner".to_owned(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(resp.owner, Some(Addr::unchecked("owner")));
+    assert_eq!(resp.pending_owner, Some(Addr::unchecked("new_owner")));
+    assert_eq!(resp.donation_denom, "uatom");
+    assert_eq!(resp.verifier, Addr::unchecked("verifier2"));
+
+    app.execute_contract(
+        Addr::unchecked("new_owner"),
+        addr.clone(),
+        &ExecuteMsg::AcceptOwnership {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("new_owner"),
+        addr.clone(),
+        &ExecuteMsg::RenounceOwnership {},
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResp = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(resp.owner, None);
+    assert_eq!(resp.pending_owner, None);
+}
@@ -1,10 +1,81 @@
+use cosmwasm_std::{Addr, StdError, Timestamp};
 use thiserror::Error;
 
+use crate::msg::RuleFailure;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
+  #[error("{0}")]
+  StdError(#[from] StdError),
+
+  #[error("Migration rejected: {}", join_failures(.failures))]
+  ValidationFailed { failures: Vec<RuleFailure> },
+
+  #[error("{sender} is not the verifier owner")]
+  Unauthorized { sender: Addr },
+
   #[error("Contract already at version {version}")]
   AlreadyMigrated { version: u32 },
 
   #[error("Invalid migration reason")]
   InvalidMigrationReason { },
+
+  #[error("Migrations may advance at most {max} versions at once")]
+  VersionJumpTooLarge { max: u32 },
+
+  #[error("{addr} is not a registered client")]
+  UnknownClient { addr: Addr },
+
+  #[error("Client is at version {expected}, not {actual}")]
+  VersionMismatch { expected: u32, actual: u32 },
+
+  #[error("Reason {reason} is not allowed for this client")]
+  ReasonNotAllowed { reason: String },
+
+  #[error("{sender} is not a signer")]
+  NotSigner { sender: Addr },
+
+  #[error("Threshold {threshold} cannot be met by {signers} signers")]
+  InvalidThreshold { threshold: u32, signers: u32 },
+
+  #[error("Migration has {approvals} of the {threshold} required approvals")]
+  NotEnoughApprovals { approvals: u32, threshold: u32 },
+
+  #[error("Migration requires a signed attestation")]
+  AttestationRequired {},
+
+  #[error("Attestation key is not trusted")]
+  UntrustedKey {},
+
+  #[error("Attestation signature is invalid")]
+  InvalidSignature {},
+
+  #[error("Nonce {nonce} was already used")]
+  NonceUsed { nonce: u64 },
+
+  #[error("Migration to version {new_version} was not announced")]
+  NotAnnounced { new_version: u32 },
+
+  #[error("Announced migration cannot pass before {ready_at}")]
+  AnnouncementPending { ready_at: Timestamp },
+
+  #[error("Migrations are only allowed during the maintenance window")]
+  OutsideMaintenanceWindow {},
+
+  #[error("Invalid maintenance window")]
+  InvalidMaintenanceWindow {},
+
+  #[error("Reason {reason} is already allowed")]
+  ReasonExists { reason: String },
+
+  #[error("Reason {reason} is not allowed")]
+  UnknownReason { reason: String },
+}
+
+fn join_failures(failures: &[RuleFailure]) -> String {
+  failures
+    .iter()
+    .map(|failure| format!("{} ({})", failure.reason, failure.rule))
+    .collect::<Vec<_>>()
+    .join(", ")
 }
@@ -1,43 +1,437 @@
-use cosmwasm_std::{entry_point, Deps, DepsMut, Env, MessageInfo, QueryResponse, Response, StdResult};
+use cosmwasm_std::{entry_point, to_json_binary, Api, Deps, Empty, DepsMut, Env, MessageInfo, Order, QueryResponse, Response, StdError, StdResult, Storage};
+use cw_storage_plus::Bound;
 
 pub mod error;
 pub mod msg;
+pub mod rules;
+pub mod state;
 
 use msg::*;
 use error::*;
+use state::*;
+use rules::{check_migration, valid_approvals, MigrationRequest};
+
+pub use rules::attestation_digest;
+
+const DEFAULT_REASON: &str = "bug_fix";
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: InstantiateMsg) -> StdResult<Response> {
-  Ok(Response::new())
+pub fn instantiate(deps: DepsMut, _env: Env, info: MessageInfo, msg: InstantiateMsg) -> Result<Response, ContractError> {
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    OWNER.save(deps.storage, &owner)?;
+    POLICY_ID.save(deps.storage, &1)?;
+
+    let reasons = msg.reasons.unwrap_or_else(|| {
+        vec![AllowedReason {
+            reason: DEFAULT_REASON.to_owned(),
+            description: "Fixes a bug".to_owned(),
+        }]
+    });
+    for allowed in reasons {
+        REASONS.save(deps.storage, &allowed.reason, &allowed.description)?;
+    }
+    MAX_VERSION_JUMP.save(deps.storage, &msg.max_version_jump)?;
+    ANNOUNCEMENT_DELAY.save(deps.storage, &msg.announcement_delay.unwrap_or(0))?;
+    save_maintenance_window(deps.storage, msg.maintenance_window)?;
+
+    let signers = msg.signers.unwrap_or(SignerSet {
+        signers: vec![],
+        threshold: 0,
+        approval_window: None,
+    });
+    save_signers(deps.storage, deps.api, signers)?;
+
+    Ok(Response::new())
+}
+
+/// Backfills the policy of verifiers deployed before it existed, keeping any
+/// settings that are already stored.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    if OWNER.may_load(deps.storage)?.is_none() {
+        let owner = match msg.owner {
+            Some(owner) => deps.api.addr_validate(&owner)?,
+            None => deps
+                .querier
+                .query_wasm_contract_info(&env.contract.address)?
+                .admin
+                .map(|admin| deps.api.addr_validate(&admin))
+                .transpose()?
+                .ok_or_else(|| StdError::not_found("owner"))?,
+        };
+        OWNER.save(deps.storage, &owner)?;
+    }
+    // Stateless verifiers only accepted `bug_fix`
+    if POLICY_ID.may_load(deps.storage)?.is_none() {
+        POLICY_ID.save(deps.storage, &1)?;
+        REASONS.save(deps.storage, DEFAULT_REASON, &"Fixes a bug".to_owned())?;
+    }
+    if MAX_VERSION_JUMP.may_load(deps.storage)?.is_none() {
+        MAX_VERSION_JUMP.save(deps.storage, &None)?;
+    }
+    if ANNOUNCEMENT_DELAY.may_load(deps.storage)?.is_none() {
+        ANNOUNCEMENT_DELAY.save(deps.storage, &0)?;
+    }
+    if MAINTENANCE_WINDOW.may_load(deps.storage)?.is_none() {
+        MAINTENANCE_WINDOW.save(deps.storage, &None)?;
+    }
+    if SIGNERS.may_load(deps.storage)?.is_none() {
+        let signers = SignerSet {
+            signers: vec![],
+            threshold: 0,
+            approval_window: None,
+        };
+        save_signers(deps.storage, deps.api, signers)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "migrate"))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let owner_only = !matches!(
+        msg,
+        ExecuteMsg::ValidateMigrationMsg { .. }
+            | ExecuteMsg::ApproveMigration { .. }
+            | ExecuteMsg::AnnounceMigration { .. }
+    );
+    if owner_only && OWNER.load(deps.storage)? != info.sender
+    {
+        return Err(ContractError::Unauthorized { sender: info.sender });
+    }
+
+    let changes_policy = matches!(
+        msg,
+        ExecuteMsg::UpdatePolicy { .. }
+            | ExecuteMsg::AddReason { .. }
+            | ExecuteMsg::RemoveReason { .. }
+            | ExecuteMsg::AddTrustedKey { .. }
+            | ExecuteMsg::RemoveTrustedKey { .. }
+            | ExecuteMsg::UpdateSigners { .. }
+    );
+    if changes_policy {
+        POLICY_ID.update(deps.storage, |id| -> StdResult<_> { Ok(id + 1) })?;
+    }
+
     match msg {
-        ExecuteMsg::ValidateMigrationMsg {current_version, new_version, reason} => {
-            if current_version >= new_version {
-                return Err(ContractError::AlreadyMigrated { version: current_version })
+        ExecuteMsg::ValidateMigrationMsg {current_version, new_version, reason, attestation} => {
+            let req = MigrationRequest {
+                client: Some(&info.sender),
+                current_version,
+                new_version,
+                reason: &reason,
+                attestation: attestation.as_ref(),
+            };
+            let failures = check_migration(deps.as_ref(), &env.block, &req)?;
+            if !failures.is_empty() {
+                return Err(ContractError::ValidationFailed { failures });
+            }
+
+            ANNOUNCEMENTS.remove(deps.storage, (&info.sender, new_version));
+            if let Some(attestation) = &attestation {
+                USED_NONCES.save(deps.storage, (&attestation.pubkey, attestation.nonce), &Empty {})?;
+            }
+
+            // Approvals are only good for a single migration
+            let signers: StdResult<Vec<_>> = APPROVALS
+                .prefix((&info.sender, new_version))
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect();
+            for signer in signers? {
+                APPROVALS.remove(deps.storage, (&info.sender, new_version, &signer));
+            }
+
+            CLIENTS.update(deps.storage, &info.sender, |client| -> StdResult<_> {
+                let mut client = client.ok_or_else(|| StdError::not_found("Client"))?;
+                client.version = new_version;
+                Ok(client)
+            })?;
+
+            let id = VALIDATIONS
+                .prefix(&info.sender)
+                .keys(deps.storage, None, None, Order::Descending)
+                .next()
+                .transpose()?
+                .map_or(1, |id| id + 1);
+            let validation = Validation {
+                id,
+                current_version,
+                new_version,
+                reason,
+                height: env.block.height,
+                time: env.block.time,
+            };
+            VALIDATIONS.save(deps.storage, (&info.sender, id), &validation)?;
+
+            let result = ValidationResult {
+                approved_version: new_version,
+                policy_id: POLICY_ID.load(deps.storage)?,
+                warnings: migration_warnings(deps.as_ref(), current_version, new_version)?,
+            };
+            Ok(Response::new().set_data(to_json_binary(&result)?))
+        }
+        ExecuteMsg::UpdatePolicy { max_version_jump, announcement_delay, maintenance_window } => {
+            MAX_VERSION_JUMP.save(deps.storage, &max_version_jump)?;
+            ANNOUNCEMENT_DELAY.save(deps.storage, &announcement_delay)?;
+            save_maintenance_window(deps.storage, maintenance_window)?;
+            let max_version_jump = max_version_jump.map_or("none".to_owned(), |max| max.to_string());
+            Ok(Response::new()
+                .add_attribute("action", "update_policy")
+                .add_attribute("max_version_jump", max_version_jump))
+        }
+        ExecuteMsg::AnnounceMigration { client, new_version } => {
+            let client = deps.api.addr_validate(&client)?;
+            if info.sender != client && OWNER.load(deps.storage)? != info.sender {
+                return Err(ContractError::Unauthorized { sender: info.sender });
+            }
+            if !CLIENTS.has(deps.storage, &client) {
+                return Err(ContractError::UnknownClient { addr: client });
             }
 
-            if reason != "bug_fix" {
-                return Err(ContractError::InvalidMigrationReason {});
+            ANNOUNCEMENTS.save(deps.storage, (&client, new_version), &env.block.time)?;
+            Ok(Response::new()
+                .add_attribute("action", "announce_migration")
+                .add_attribute("client", client.as_str())
+                .add_attribute("new_version", new_version.to_string()))
+        }
+        ExecuteMsg::AddReason { reason, description } => {
+            if REASONS.has(deps.storage, &reason) {
+                return Err(ContractError::ReasonExists { reason });
+            }
+            REASONS.save(deps.storage, &reason, &description)?;
+            Ok(Response::new()
+                .add_attribute("action", "add_reason")
+                .add_attribute("reason", reason))
+        }
+        ExecuteMsg::RemoveReason { reason } => {
+            if !REASONS.has(deps.storage, &reason) {
+                return Err(ContractError::UnknownReason { reason });
+            }
+            REASONS.remove(deps.storage, &reason);
+            Ok(Response::new()
+                .add_attribute("action", "remove_reason")
+                .add_attribute("reason", reason))
+        }
+        ExecuteMsg::RegisterClient { contract, name, allowed_reasons, version } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            for reason in &allowed_reasons {
+                if !REASONS.has(deps.storage, reason) {
+                    return Err(ContractError::UnknownReason { reason: reason.clone() });
+                }
             }
 
-            Ok(Response::new())
+            let client = Client {
+                contract: contract.clone(),
+                name,
+                allowed_reasons,
+                version,
+            };
+            CLIENTS.save(deps.storage, &contract, &client)?;
+            Ok(Response::new()
+                .add_attribute("action", "register_client")
+                .add_attribute("contract", contract.as_str()))
+        }
+        ExecuteMsg::AddTrustedKey { pubkey, key_type } => {
+            TRUSTED_KEYS.save(deps.storage, &pubkey, &key_type)?;
+            Ok(Response::new()
+                .add_attribute("action", "add_trusted_key")
+                .add_attribute("pubkey", pubkey.to_base64()))
+        }
+        ExecuteMsg::RemoveTrustedKey { pubkey } => {
+            if !TRUSTED_KEYS.has(deps.storage, &pubkey) {
+                return Err(ContractError::UntrustedKey {});
+            }
+            TRUSTED_KEYS.remove(deps.storage, &pubkey);
+            Ok(Response::new()
+                .add_attribute("action", "remove_trusted_key")
+                .add_attribute("pubkey", pubkey.to_base64()))
+        }
+        ExecuteMsg::UpdateSigners { signers } => {
+            let threshold = signers.threshold;
+            save_signers(deps.storage, deps.api, signers)?;
+            Ok(Response::new()
+                .add_attribute("action", "update_signers")
+                .add_attribute("threshold", threshold.to_string()))
+        }
+        ExecuteMsg::ApproveMigration { client, new_version } => {
+            if !SIGNERS.load(deps.storage)?.contains(&info.sender) {
+                return Err(ContractError::NotSigner { sender: info.sender });
+            }
+            let client = deps.api.addr_validate(&client)?;
+            if !CLIENTS.has(deps.storage, &client) {
+                return Err(ContractError::UnknownClient { addr: client });
+            }
+
+            APPROVALS.save(deps.storage, (&client, new_version, &info.sender), &env.block.time)?;
+            Ok(Response::new()
+                .add_attribute("action", "approve_migration")
+                .add_attribute("signer", info.sender.as_str())
+                .add_attribute("client", client.as_str())
+                .add_attribute("new_version", new_version.to_string()))
+        }
+        ExecuteMsg::DeregisterClient { contract } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            if !CLIENTS.has(deps.storage, &contract) {
+                return Err(ContractError::UnknownClient { addr: contract });
+            }
+            CLIENTS.remove(deps.storage, &contract);
+            Ok(Response::new()
+                .add_attribute("action", "deregister_client")
+                .add_attribute("contract", contract.as_str()))
         }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(
-    _deps: Deps,
-    _env: Env,
-    _msg: QueryMsg,
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
 ) -> StdResult<QueryResponse> {
-  Ok(QueryResponse::default())
+    match msg {
+        QueryMsg::Policy {} => to_json_binary(&PolicyResp {
+            owner: OWNER.load(deps.storage)?,
+            policy_id: POLICY_ID.load(deps.storage)?,
+            max_version_jump: MAX_VERSION_JUMP.load(deps.storage)?,
+            announcement_delay: ANNOUNCEMENT_DELAY.load(deps.storage)?,
+            maintenance_window: MAINTENANCE_WINDOW.load(deps.storage)?,
+        }),
+        QueryMsg::AllowedReasons {} => {
+            let reasons: StdResult<Vec<_>> = REASONS
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(reason, description)| AllowedReason { reason, description }))
+                .collect();
+            to_json_binary(&AllowedReasonsResp { reasons: reasons? })
+        }
+        QueryMsg::ValidationHistory { contract, start_after, limit } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+
+            let validations: StdResult<Vec<_>> = VALIDATIONS
+                .prefix(&contract)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, validation)| validation))
+                .collect();
+            to_json_binary(&ValidationHistoryResp { validations: validations? })
+        }
+        QueryMsg::Client { contract } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            to_json_binary(&ClientResp {
+                client: CLIENTS.may_load(deps.storage, &contract)?,
+            })
+        }
+        QueryMsg::Clients { start_after, limit } => {
+            let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_ref().map(Bound::exclusive);
+
+            let clients: StdResult<Vec<_>> = CLIENTS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, client)| client))
+                .collect();
+            to_json_binary(&ClientsResp { clients: clients? })
+        }
+        QueryMsg::Signers {} => to_json_binary(&SignersResp {
+            signers: SIGNERS.load(deps.storage)?,
+            threshold: SIGNER_THRESHOLD.load(deps.storage)?,
+            approval_window: APPROVAL_WINDOW.load(deps.storage)?,
+        }),
+        QueryMsg::Announcement { client, new_version } => {
+            let client = deps.api.addr_validate(&client)?;
+            to_json_binary(&AnnouncementResp {
+                announced_at: ANNOUNCEMENTS.may_load(deps.storage, (&client, new_version))?,
+            })
+        }
+        QueryMsg::Approvals { client, new_version } => {
+            let client = deps.api.addr_validate(&client)?;
+            let approvals = valid_approvals(deps, &env.block, &client, new_version)?;
+            to_json_binary(&ApprovalsResp { approvals })
+        }
+        QueryMsg::ValidateMigration {current_version, new_version, reason, client, attestation} => {
+            let client = client.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+            let req = MigrationRequest {
+                client: client.as_ref(),
+                current_version,
+                new_version,
+                reason: &reason,
+                attestation: attestation.as_ref(),
+            };
+            let failures = check_migration(deps, &env.block, &req)?;
+            to_json_binary(&MigrationVerdict {
+                accepted: failures.is_empty(),
+                reasons: failures.iter().map(|failure| failure.reason.clone()).collect(),
+                failures,
+            })
+        }
+    }
+}
+
+fn save_signers(storage: &mut dyn Storage, api: &dyn Api, set: SignerSet) -> Result<(), ContractError> {
+    let mut signers = set
+        .signers
+        .iter()
+        .map(|signer| api.addr_validate(signer))
+        .collect::<StdResult<Vec<_>>>()?;
+    // A signer listed twice would count twice towards the threshold
+    signers.sort();
+    signers.dedup();
+    if set.threshold as usize > signers.len() {
+        return Err(ContractError::InvalidThreshold {
+            threshold: set.threshold,
+            signers: signers.len() as u32,
+        });
+    }
+
+    SIGNERS.save(storage, &signers)?;
+    SIGNER_THRESHOLD.save(storage, &set.threshold)?;
+    APPROVAL_WINDOW.save(storage, &set.approval_window)?;
+    Ok(())
+}
+
+fn save_maintenance_window(storage: &mut dyn Storage, window: Option<MaintenanceWindow>) -> Result<(), ContractError> {
+    if let Some(window) = &window {
+        let valid = window.start_hour < 24
+            && window.end_hour < 24
+            && window.weekdays.iter().all(|day| *day < 7);
+        if !valid {
+            return Err(ContractError::InvalidMaintenanceWindow {});
+        }
+    }
+    MAINTENANCE_WINDOW.save(storage, &window)?;
+    Ok(())
+}
+
+/// Policy gaps worth flagging on an accepted migration
+fn migration_warnings(deps: Deps, current_version: u32, new_version: u32) -> StdResult<Vec<String>> {
+    let mut warnings = vec![];
+
+    if new_version - current_version > 1 {
+        warnings.push(format!("Jumps {} versions at once", new_version - current_version));
+    }
+    if SIGNER_THRESHOLD.load(deps.storage)? == 0 {
+        warnings.push("No signer approvals required".to_owned());
+    }
+    let no_keys = TRUSTED_KEYS
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    if no_keys {
+        warnings.push("No attestation required".to_owned());
+    }
+
+    Ok(warnings)
 }
@@ -1,4 +1,5 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Timestamp};
 
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -6,11 +7,271 @@ pub enum ExecuteMsg {
       current_version: u32,
       new_version: u32,
       reason: String,
+      /// Required once any trusted keys are registered
+      #[serde(default, skip_serializing_if = "Option::is_none")]
+      attestation: Option<Attestation>,
+  },
+  /// Replaces the policy, owner only
+  UpdatePolicy {
+      max_version_jump: Option<u32>,
+      /// Seconds between announcing a migration and it passing, no announcement needed if 0
+      announcement_delay: u64,
+      maintenance_window: Option<MaintenanceWindow>,
+  },
+  /// Records the intent to migrate `client` to `new_version`, by the owner or the client
+  AnnounceMigration {
+      client: String,
+      new_version: u32,
+  },
+  /// Allows migrating for `reason`, owner only
+  AddReason {
+      reason: String,
+      description: String,
+  },
+  RemoveReason {
+      reason: String,
+  },
+  /// Trusts `pubkey` to sign attestations, owner only
+  AddTrustedKey {
+      pubkey: Binary,
+      key_type: KeyType,
+  },
+  RemoveTrustedKey {
+      pubkey: Binary,
+  },
+  /// Lets `contract` request validations starting from `version`, owner only
+  RegisterClient {
+      contract: String,
+      name: String,
+      /// Subset of the allowed reasons this client may migrate for, any if empty
+      allowed_reasons: Vec<String>,
+      version: u32,
+  },
+  DeregisterClient {
+      contract: String,
+  },
+  /// Replaces the approvers, owner only
+  UpdateSigners {
+      signers: SignerSet,
+  },
+  /// Approves migrating `client` to `new_version`, signers only
+  ApproveMigration {
+      client: String,
+      new_version: u32,
+  },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+  #[returns(PolicyResp)]
+  Policy {},
+  #[returns(AllowedReasonsResp)]
+  AllowedReasons {},
+  /// Accepted validations requested by `contract`, rejected ones are reverted with the request
+  #[returns(ValidationHistoryResp)]
+  ValidationHistory {
+      contract: String,
+      start_after: Option<u64>,
+      limit: Option<u32>,
+  },
+  #[returns(ClientResp)]
+  Client {
+      contract: String,
+  },
+  #[returns(ClientsResp)]
+  Clients {
+      start_after: Option<String>,
+      limit: Option<u32>,
+  },
+  #[returns(SignersResp)]
+  Signers {},
+  #[returns(AnnouncementResp)]
+  Announcement {
+      client: String,
+      new_version: u32,
+  },
+  /// Unexpired approvals for migrating `client` to `new_version`
+  #[returns(ApprovalsResp)]
+  Approvals {
+      client: String,
+      new_version: u32,
+  },
+  /// Runs the same checks as `ExecuteMsg::ValidateMigrationMsg` without failing.
+  /// The registry, quorum, attestation and announcement rules all need the
+  /// requesting contract, so they are only checked if `client` is given
+  #[returns(MigrationVerdict)]
+  ValidateMigration {
+      current_version: u32,
+      new_version: u32,
+      reason: String,
+      client: Option<String>,
+      attestation: Option<Attestation>,
   }
 }
 
 #[cw_serde]
-pub enum QueryMsg { }
+pub struct MigrationVerdict {
+  pub accepted: bool,
+  /// Every check the migration fails, empty when accepted
+  pub reasons: Vec<String>,
+  /// `reasons` along with the rule each comes from
+  pub failures: Vec<RuleFailure>,
+}
+
+#[cw_serde]
+pub struct RuleFailure {
+  pub rule: String,
+  pub reason: String,
+}
+
+#[cw_serde]
+pub struct AllowedReason {
+  pub reason: String,
+  pub description: String,
+}
+
+/// UTC time span migrations are allowed in
+#[cw_serde]
+pub struct MaintenanceWindow {
+  /// Days of the week, 0 being Monday, any day if empty
+  pub weekdays: Vec<u8>,
+  pub start_hour: u8,
+  /// Exclusive, wraps past midnight if not after `start_hour`
+  pub end_hour: u8,
+}
+
+/// Set as the response data of an accepted `ValidateMigrationMsg`
+#[cw_serde]
+pub struct ValidationResult {
+  pub approved_version: u32,
+  /// Revision of the policy the migration was checked against
+  pub policy_id: u64,
+  pub warnings: Vec<String>,
+}
+
+#[cw_serde]
+pub struct PolicyResp {
+  pub owner: Addr,
+  pub policy_id: u64,
+  pub max_version_jump: Option<u32>,
+  pub announcement_delay: u64,
+  pub maintenance_window: Option<MaintenanceWindow>,
+}
+
+#[cw_serde]
+pub struct AllowedReasonsResp {
+  pub reasons: Vec<AllowedReason>,
+}
+
+#[cw_serde]
+pub struct Validation {
+  pub id: u64,
+  pub current_version: u32,
+  pub new_version: u32,
+  pub reason: String,
+  pub height: u64,
+  pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct ValidationHistoryResp {
+  pub validations: Vec<Validation>,
+}
+
+#[cw_serde]
+pub struct Client {
+  pub contract: Addr,
+  pub name: String,
+  pub allowed_reasons: Vec<String>,
+  /// Version the client is at according to its last accepted validation
+  pub version: u32,
+}
+
+#[cw_serde]
+pub struct ClientResp {
+  pub client: Option<Client>,
+}
 
 #[cw_serde]
-pub struct InstantiateMsg { }
+pub struct ClientsResp {
+  pub clients: Vec<Client>,
+}
+
+#[cw_serde]
+pub struct SignerSet {
+  pub signers: Vec<String>,
+  /// Approvals needed for a migration to pass, approvals are not required if 0
+  pub threshold: u32,
+  /// Seconds an approval stays valid for, forever if `None`
+  pub approval_window: Option<u64>,
+}
+
+#[cw_serde]
+pub struct SignersResp {
+  pub signers: Vec<Addr>,
+  pub threshold: u32,
+  pub approval_window: Option<u64>,
+}
+
+#[cw_serde]
+pub struct AnnouncementResp {
+  pub announced_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct Approval {
+  pub signer: Addr,
+  pub approved_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct ApprovalsResp {
+  pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum KeyType {
+  Secp256k1,
+  Ed25519,
+}
+
+/// Release engineer's offline approval of a migration
+#[cw_serde]
+pub struct Attestation {
+  pub pubkey: Binary,
+  /// Signature over the sha256 digest of the JSON encoded `AttestationPayload`
+  pub signature: Binary,
+  /// Single use per key
+  pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct AttestationPayload {
+  pub client: Addr,
+  pub current_version: u32,
+  pub new_version: u32,
+  pub reason: String,
+  pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+  /// Defaults to the instantiator
+  pub owner: Option<String>,
+  /// Defaults to only allowing `bug_fix`
+  pub reasons: Option<Vec<AllowedReason>>,
+  pub max_version_jump: Option<u32>,
+  /// Defaults to no approvals being required
+  pub signers: Option<SignerSet>,
+  /// Defaults to 0
+  pub announcement_delay: Option<u64>,
+  pub maintenance_window: Option<MaintenanceWindow>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+  /// Only used when no owner is stored yet, defaults to the contract admin
+  pub owner: Option<String>,
+}
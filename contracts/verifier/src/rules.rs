@@ -0,0 +1,381 @@
+use cosmwasm_std::{to_json_vec, Addr, BlockInfo, Deps, Order, StdResult, Timestamp};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{Approval, Attestation, AttestationPayload, Client, KeyType, MaintenanceWindow, RuleFailure};
+use crate::state::*;
+
+/// Migration being validated
+pub struct MigrationRequest<'a> {
+    /// Requesting contract, client rules are skipped without one
+    pub client: Option<&'a Addr>,
+    pub current_version: u32,
+    pub new_version: u32,
+    pub reason: &'a str,
+    pub attestation: Option<&'a Attestation>,
+}
+
+/// Single check a migration has to pass.
+pub trait ValidationRule {
+    fn name(&self) -> &'static str;
+
+    /// Every way `req` breaks the rule, empty if it passes
+    fn check(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        req: &MigrationRequest,
+        client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>>;
+}
+
+/// The requesting contract is registered, at the expected version and allowed the reason
+pub struct Registry;
+
+impl ValidationRule for Registry {
+    fn name(&self) -> &'static str {
+        "registry"
+    }
+
+    fn check(
+        &self,
+        _deps: Deps,
+        _block: &BlockInfo,
+        req: &MigrationRequest,
+        client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        let addr = match req.client {
+            Some(addr) => addr,
+            None => return Ok(vec![]),
+        };
+        let client = match client {
+            Some(client) => client,
+            None => return Ok(vec![ContractError::UnknownClient { addr: addr.clone() }]),
+        };
+
+        let mut errors = vec![];
+        if client.version != req.current_version {
+            errors.push(ContractError::VersionMismatch {
+                expected: client.version,
+                actual: req.current_version,
+            });
+        }
+        let allowed = client.allowed_reasons.is_empty()
+            || client.allowed_reasons.iter().any(|allowed| allowed == req.reason);
+        if !allowed {
+            errors.push(ContractError::ReasonNotAllowed { reason: req.reason.to_owned() });
+        }
+        Ok(errors)
+    }
+}
+
+/// Enough signers approved the migration within the approval window
+pub struct Quorum {
+    pub threshold: u32,
+}
+
+impl ValidationRule for Quorum {
+    fn name(&self) -> &'static str {
+        "quorum"
+    }
+
+    fn check(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        req: &MigrationRequest,
+        client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        let client = match client {
+            Some(client) => client,
+            None => return Ok(vec![]),
+        };
+
+        let approvals = valid_approvals(deps, block, &client.contract, req.new_version)?.len() as u32;
+        if approvals < self.threshold {
+            return Ok(vec![ContractError::NotEnoughApprovals {
+                approvals,
+                threshold: self.threshold,
+            }]);
+        }
+        Ok(vec![])
+    }
+}
+
+/// A trusted key signed off on the migration, once trusted keys are registered
+pub struct SignedAttestation;
+
+impl ValidationRule for SignedAttestation {
+    fn name(&self) -> &'static str {
+        "attestation"
+    }
+
+    fn check(
+        &self,
+        deps: Deps,
+        _block: &BlockInfo,
+        req: &MigrationRequest,
+        client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        let client = match client {
+            Some(client) => client,
+            None => return Ok(vec![]),
+        };
+
+        let attestation = match req.attestation {
+            Some(attestation) => attestation,
+            None => {
+                let no_keys = TRUSTED_KEYS
+                    .keys_raw(deps.storage, None, None, Order::Ascending)
+                    .next()
+                    .is_none();
+                return Ok((!no_keys).then_some(ContractError::AttestationRequired {}).into_iter().collect());
+            }
+        };
+
+        let key_type = match TRUSTED_KEYS.may_load(deps.storage, &attestation.pubkey)? {
+            Some(key_type) => key_type,
+            None => return Ok(vec![ContractError::UntrustedKey {}]),
+        };
+        if USED_NONCES.has(deps.storage, (&attestation.pubkey, attestation.nonce)) {
+            return Ok(vec![ContractError::NonceUsed { nonce: attestation.nonce }]);
+        }
+
+        let digest = attestation_digest(&AttestationPayload {
+            client: client.contract.clone(),
+            current_version: req.current_version,
+            new_version: req.new_version,
+            reason: req.reason.to_owned(),
+            nonce: attestation.nonce,
+        })?;
+        let verified = match key_type {
+            KeyType::Secp256k1 => deps.api.secp256k1_verify(&digest, &attestation.signature, &attestation.pubkey),
+            KeyType::Ed25519 => deps.api.ed25519_verify(&digest, &attestation.signature, &attestation.pubkey),
+        };
+        match verified {
+            Ok(true) => Ok(vec![]),
+            _ => Ok(vec![ContractError::InvalidSignature {}]),
+        }
+    }
+}
+
+/// The migration was announced at least `delay` seconds ago
+pub struct Announcement {
+    pub delay: u64,
+}
+
+impl ValidationRule for Announcement {
+    fn name(&self) -> &'static str {
+        "announcement"
+    }
+
+    fn check(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        req: &MigrationRequest,
+        client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        let client = match client {
+            Some(client) => client,
+            None => return Ok(vec![]),
+        };
+
+        match ANNOUNCEMENTS.may_load(deps.storage, (&client.contract, req.new_version))? {
+            None => Ok(vec![ContractError::NotAnnounced { new_version: req.new_version }]),
+            Some(announced_at) => {
+                let ready_at = announced_at.plus_seconds(self.delay);
+                if block.time < ready_at {
+                    return Ok(vec![ContractError::AnnouncementPending { ready_at }]);
+                }
+                Ok(vec![])
+            }
+        }
+    }
+}
+
+/// Versions only move forward
+pub struct Monotonic;
+
+impl ValidationRule for Monotonic {
+    fn name(&self) -> &'static str {
+        "monotonic"
+    }
+
+    fn check(
+        &self,
+        _deps: Deps,
+        _block: &BlockInfo,
+        req: &MigrationRequest,
+        _client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        if req.current_version >= req.new_version {
+            return Ok(vec![ContractError::AlreadyMigrated { version: req.current_version }]);
+        }
+        Ok(vec![])
+    }
+}
+
+/// Versions move forward by at most `max` at once
+pub struct MaxJump {
+    pub max: u32,
+}
+
+impl ValidationRule for MaxJump {
+    fn name(&self) -> &'static str {
+        "max_jump"
+    }
+
+    fn check(
+        &self,
+        _deps: Deps,
+        _block: &BlockInfo,
+        req: &MigrationRequest,
+        _client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        if req.new_version.saturating_sub(req.current_version) > self.max {
+            return Ok(vec![ContractError::VersionJumpTooLarge { max: self.max }]);
+        }
+        Ok(vec![])
+    }
+}
+
+/// The reason is on the allowlist
+pub struct ReasonAllowlist;
+
+impl ValidationRule for ReasonAllowlist {
+    fn name(&self) -> &'static str {
+        "reason_allowlist"
+    }
+
+    fn check(
+        &self,
+        deps: Deps,
+        _block: &BlockInfo,
+        req: &MigrationRequest,
+        _client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        if !REASONS.has(deps.storage, req.reason) {
+            return Ok(vec![ContractError::InvalidMigrationReason {}]);
+        }
+        Ok(vec![])
+    }
+}
+
+/// The block time falls within the maintenance window
+pub struct TimeWindow {
+    pub window: MaintenanceWindow,
+}
+
+impl ValidationRule for TimeWindow {
+    fn name(&self) -> &'static str {
+        "time_window"
+    }
+
+    fn check(
+        &self,
+        _deps: Deps,
+        block: &BlockInfo,
+        _req: &MigrationRequest,
+        _client: Option<&Client>,
+    ) -> StdResult<Vec<ContractError>> {
+        if !in_maintenance_window(&self.window, block.time) {
+            return Ok(vec![ContractError::OutsideMaintenanceWindow {}]);
+        }
+        Ok(vec![])
+    }
+}
+
+/// Rules enabled by the stored policy, in the order they are evaluated
+pub fn rules(deps: Deps) -> StdResult<Vec<Box<dyn ValidationRule>>> {
+    let mut rules: Vec<Box<dyn ValidationRule>> = vec![Box::new(Registry)];
+
+    let threshold = SIGNER_THRESHOLD.load(deps.storage)?;
+    if threshold > 0 {
+        rules.push(Box::new(Quorum { threshold }));
+    }
+    rules.push(Box::new(SignedAttestation));
+    let delay = ANNOUNCEMENT_DELAY.load(deps.storage)?;
+    if delay > 0 {
+        rules.push(Box::new(Announcement { delay }));
+    }
+
+    rules.push(Box::new(Monotonic));
+    if let Some(max) = MAX_VERSION_JUMP.load(deps.storage)? {
+        rules.push(Box::new(MaxJump { max }));
+    }
+    rules.push(Box::new(ReasonAllowlist));
+    if let Some(window) = MAINTENANCE_WINDOW.load(deps.storage)? {
+        rules.push(Box::new(TimeWindow { window }));
+    }
+
+    Ok(rules)
+}
+
+/// Every failing rule, in the order they are evaluated
+pub fn check_migration(
+    deps: Deps,
+    block: &BlockInfo,
+    req: &MigrationRequest,
+) -> StdResult<Vec<RuleFailure>> {
+    let client = req
+        .client
+        .map(|addr| CLIENTS.may_load(deps.storage, addr))
+        .transpose()?
+        .flatten();
+
+    let mut failures = vec![];
+    for rule in rules(deps)? {
+        for err in rule.check(deps, block, req, client.as_ref())? {
+            failures.push(RuleFailure {
+                rule: rule.name().to_owned(),
+                reason: err.to_string(),
+            });
+        }
+    }
+    Ok(failures)
+}
+
+fn in_maintenance_window(window: &MaintenanceWindow, time: Timestamp) -> bool {
+    let hours = time.seconds() / 3600;
+    // The epoch fell on a Thursday
+    let weekday = ((hours / 24 + 3) % 7) as u8;
+    let hour = (hours % 24) as u8;
+
+    let day_allowed = window.weekdays.is_empty() || window.weekdays.contains(&weekday);
+    let hour_allowed = if window.start_hour < window.end_hour {
+        (window.start_hour..window.end_hour).contains(&hour)
+    } else {
+        hour >= window.start_hour || hour < window.end_hour
+    };
+    day_allowed && hour_allowed
+}
+
+/// Approvals from current signers which are still within the approval window
+pub fn valid_approvals(
+    deps: Deps,
+    block: &BlockInfo,
+    client: &Addr,
+    new_version: u32,
+) -> StdResult<Vec<Approval>> {
+    let signers = SIGNERS.load(deps.storage)?;
+    let window = APPROVAL_WINDOW.load(deps.storage)?;
+
+    APPROVALS
+        .prefix((client, new_version))
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((signer, approved_at)) => {
+                signers.contains(signer)
+                    && window.is_none_or(|window| approved_at.plus_seconds(window) > block.time)
+            }
+            Err(_) => true,
+        })
+        .map(|item| item.map(|(signer, approved_at)| Approval { signer, approved_at }))
+        .collect()
+}
+
+/// Digest attestations are signed over
+pub fn attestation_digest(payload: &AttestationPayload) -> StdResult<Vec<u8>> {
+    Ok(Sha256::digest(to_json_vec(payload)?).to_vec())
+}
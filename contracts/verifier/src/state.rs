@@ -0,0 +1,27 @@
+use cosmwasm_std::{Addr, Empty, Timestamp};
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::{Client, KeyType, MaintenanceWindow, Validation};
+
+pub const OWNER: Item<Addr> = Item::new("owner");
+/// Bumped on every policy change
+pub const POLICY_ID: Item<u64> = Item::new("policy_id");
+/// Accepted migration reasons with their descriptions
+pub const REASONS: Map<&str, String> = Map::new("reasons");
+/// Largest number of versions a single migration may skip, unlimited if `None`
+pub const MAX_VERSION_JUMP: Item<Option<u32>> = Item::new("max_version_jump");
+pub const ANNOUNCEMENT_DELAY: Item<u64> = Item::new("announcement_delay");
+pub const MAINTENANCE_WINDOW: Item<Option<MaintenanceWindow>> = Item::new("maintenance_window");
+/// When migrating a client to a version was announced
+pub const ANNOUNCEMENTS: Map<(&Addr, u32), Timestamp> = Map::new("announcements");
+/// Accepted validations, keyed by the requesting contract and a per contract id
+pub const VALIDATIONS: Map<(&Addr, u64), Validation> = Map::new("validations");
+pub const CLIENTS: Map<&Addr, Client> = Map::new("clients");
+pub const SIGNERS: Item<Vec<Addr>> = Item::new("signers");
+pub const SIGNER_THRESHOLD: Item<u32> = Item::new("signer_threshold");
+pub const APPROVAL_WINDOW: Item<Option<u64>> = Item::new("approval_window");
+/// When each signer approved migrating a client to a version
+pub const APPROVALS: Map<(&Addr, u32, &Addr), Timestamp> = Map::new("approvals");
+/// Keys trusted to sign attestations, keyed by the raw public key
+pub const TRUSTED_KEYS: Map<&[u8], KeyType> = Map::new("trusted_keys");
+pub const USED_NONCES: Map<(&[u8], u64), Empty> = Map::new("used_nonces");